@@ -0,0 +1,61 @@
+//! Tests which place scones attributes between other attributes, to make sure the result does not
+//! depend on where they are relative to each other.
+
+#[test]
+pub fn attribute_macro_between_demo() {
+    use scones::{make_builder, make_constructor};
+
+    #[make_builder((b?))]
+    /// ^ Documentation for the builder.
+    #[rustversion::since(1.31)]
+    /// ^ More documentation for the builder, even though another attribute macro came first.
+    #[make_constructor(pub with_a(a: i32))]
+    /// ^ Documentation for the constructor.
+    #[derive(Debug, PartialEq)]
+    struct Interleaved {
+        #[value(a for with_a)]
+        a: i32,
+        #[value(0)]
+        b: i32,
+    }
+
+    assert_eq!(
+        InterleavedBuilder::new().a(1).b(2).build(),
+        Interleaved { a: 1, b: 2 }
+    );
+    assert_eq!(Interleaved::with_a(3), Interleaved { a: 3, b: 0 });
+}
+
+#[test]
+pub fn attribute_macro_first_demo() {
+    use scones::{make_builder, make_constructor};
+
+    #[rustversion::since(1.31)]
+    #[derive(Debug, PartialEq)]
+    #[make_constructor]
+    #[allow(dead_code)]
+    #[make_builder]
+    #[rustversion::since(1.31)]
+    struct AfterOthers(#[value(12 for AfterOthersBuilder)] i32);
+
+    assert_eq!(AfterOthers::new(1), AfterOthers(1));
+    assert_eq!(AfterOthersBuilder::new().build(), AfterOthers(12));
+}
+
+#[test]
+pub fn derive_between_demo() {
+    #[scones::make_constructor]
+    #[derive(Clone, Debug, PartialEq)]
+    #[::scones::make_builder]
+    /// ^ Documentation for the builder, placed after a derive.
+    #[repr(C)]
+    #[scones::make_constructor(pub doubled(half: u8))]
+    struct AroundDerive {
+        #[value(half * 2 for doubled)]
+        data: u8,
+    }
+
+    let instance = AroundDeriveBuilder::new().data(4).build();
+    assert_eq!(instance.clone(), AroundDerive::new(4));
+    assert_eq!(instance, AroundDerive::doubled(2));
+}
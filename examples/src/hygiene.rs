@@ -0,0 +1,97 @@
+//! Structs which use names that could collide with identifiers generated by the macros. These
+//! exist to make sure the generated code keeps compiling when users pick unfortunate names.
+#![allow(non_snake_case, non_upper_case_globals)]
+
+use scones::{make_builder, make_constructor};
+
+/// Fields named after the builder's own functions and internal fields. The setter for `build` is
+/// named `build_`, and the setter for `new` is named `new__` since `new_` is already taken.
+#[make_builder]
+#[make_constructor]
+pub struct ReservedNames {
+    pub value: i32,
+    pub new: i32,
+    pub build: i32,
+    pub new_: i32,
+    pub generics_marker__: i32,
+}
+
+/// Generic parameters named like the parameters the builder adds to track which fields are set
+/// and which type a setter changes to.
+#[make_builder]
+#[make_constructor]
+pub struct OverlappingGenerics<DataStatus__, NewDataStatus__, const OtherStatus__: usize> {
+    pub data: DataStatus__,
+    pub other: NewDataStatus__,
+    #[value(OtherStatus__)]
+    pub count: usize,
+}
+
+/// Fields whose status parameters would have the same name.
+#[make_builder]
+pub struct SimilarNames {
+    pub a_b: i32,
+    pub a__b: i32,
+    pub a_b_: i32,
+}
+
+/// Custom parameters named like things the generated code could use.
+#[make_builder((self_: i32, value: Option<i32>))]
+#[make_constructor(pub with_custom(self_: i32, value: i32))]
+pub struct CustomNames {
+    #[value(self_ + value.unwrap_or(0))]
+    #[value(self_ + value for with_custom)]
+    pub total: i32,
+}
+
+/// Parameters named like the field which holds the generic parameters of the values read by
+/// `Deserialize`.
+#[make_builder(serde)]
+pub struct DeserializedNames<T> {
+    pub generics_marker__: T,
+    pub marker__: i32,
+}
+
+#[test]
+pub fn hygiene_demo() {
+    let instance = ReservedNamesBuilder::new()
+        .value(1)
+        .new__(2)
+        .build_(3)
+        .new_(4)
+        .generics_marker__(5)
+        .build();
+    assert_eq!(
+        (instance.value, instance.new, instance.build, instance.new_),
+        (1, 2, 3, 4)
+    );
+    assert_eq!(instance.generics_marker__, 5);
+    let instance = ReservedNames::new(1, 2, 3, 4, 5);
+    assert_eq!(instance.build, 3);
+
+    let instance: OverlappingGenerics<_, _, 3> = OverlappingGenericsBuilder::new()
+        .data(1)
+        .other("two")
+        .build();
+    assert_eq!(
+        (instance.data, instance.other, instance.count),
+        (1, "two", 3)
+    );
+    let instance = OverlappingGenerics::<_, _, 4>::new(1.0, 'c');
+    assert_eq!(instance.count, 4);
+
+    let instance = SimilarNamesBuilder::new().a_b(1).a__b(2).a_b_(3).build();
+    assert_eq!((instance.a_b, instance.a__b, instance.a_b_), (1, 2, 3));
+
+    let instance = CustomNamesBuilder::new().self_(1).value(2).build();
+    assert_eq!(instance.total, 3);
+    let instance = CustomNames::with_custom(4, 5);
+    assert_eq!(instance.total, 9);
+
+    let json = r#"{ "generics_marker__": "a", "marker__": 1 }"#;
+    let instance: DeserializedNames<String> = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        (&instance.generics_marker__[..], instance.marker__),
+        ("a", 1)
+    );
+}
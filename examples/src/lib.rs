@@ -1,4 +1,10 @@
-use scones::{make_builder, make_constructor};
+use scones::{
+    make_accessors, make_builder, make_constructor, make_describe, make_factory, make_newtype,
+    Builder, Constructor,
+};
+
+pub mod composition;
+pub mod hygiene;
 
 /// A basic example which generates a default constructor.
 ///
@@ -160,6 +166,7 @@ pub fn return_result_demo() {
 #[make_constructor]
 #[make_constructor(pub default_number)]
 #[make_builder((field_1?))]
+#[allow(dead_code)]
 pub struct TupleStruct(
     #[value(30 for default_number)] i32,
     #[value("Unnamed".to_owned() for TupleStructBuilder)] String,
@@ -307,6 +314,1730 @@ pub fn templated_demo() {
     assert_eq!(instance.data, "Hello World!");
 }
 
+#[test]
+pub fn generic_new_demo() {
+    use scones::Missing;
+
+    // new() is generic over the struct's parameters, so they can be chosen up front.
+    let builder: TemplatedBuilder<u32, Missing> = TemplatedBuilder::new();
+    assert_eq!(builder.data(5).build().data, 5);
+    let builder: TemplatedTryBuilder<&str, Missing> = TemplatedTryBuilder::new();
+    assert_eq!(builder.data("text").build().unwrap().data, "text");
+}
+
+/// An example showing a builder whose setters can change the struct's type parameters.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder(untyped)]
+/// pub struct Tagged<'a, T: ?Sized, U: Clone> {
+///     pub name: &'a T,
+///     pub alias: &'a T,
+///     pub tag: U,
+/// }
+/// ```
+#[make_builder(untyped)]
+pub struct Tagged<'a, T: ?Sized, U: Clone> {
+    pub name: &'a T,
+    pub alias: &'a T,
+    pub tag: U,
+}
+
+#[test]
+pub fn type_changing_demo() {
+    use scones::{Missing, Present};
+
+    // The builder starts with a placeholder for U, which the setter for tag replaces.
+    let builder: TaggedBuilder<str, (), Missing, Missing, Missing> = TaggedBuilder::new();
+    let builder: TaggedBuilder<str, u32, Missing, Missing, Present> = builder.tag(5u32);
+    let instance = builder.tag("label").name("a").alias("b").build();
+    assert_eq!(
+        (instance.name, instance.alias, instance.tag),
+        ("a", "b", "label")
+    );
+
+    // T is mentioned by more than one field, so it cannot be changed and is chosen up front.
+    let builder: TaggedBuilder<str, (), Present, Missing, Missing> = TaggedBuilder::new().name("a");
+    assert_eq!(builder.alias("b").tag(1).build().tag, 1);
+}
+
 #[make_constructor]
 #[derive(Debug)]
+#[allow(dead_code)]
 struct SconesAndDerive {}
+
+/// Increments a shared counter when dropped. Used to check that builders drop exactly the values
+/// they own.
+pub struct DropCounter(pub std::rc::Rc<std::cell::Cell<usize>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[make_builder]
+pub struct DropTracking {
+    pub first: DropCounter,
+    pub second: DropCounter,
+}
+
+#[test]
+pub fn builder_drop_demo() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let drops = Rc::new(Cell::new(0));
+    let counter = || DropCounter(Rc::clone(&drops));
+
+    // Dropping a builder with no values set must not touch uninitialized memory.
+    drop(DropTrackingBuilder::new());
+    assert_eq!(drops.get(), 0);
+
+    // Dropping a partially built builder drops only the values that were provided.
+    drop(DropTrackingBuilder::new().first(counter()));
+    assert_eq!(drops.get(), 1);
+
+    // Setting a value twice drops the value it replaces.
+    let builder = DropTrackingBuilder::new().first(counter()).first(counter());
+    assert_eq!(drops.get(), 2);
+
+    // Building moves the values into the instance without dropping them.
+    let instance = builder.second(counter()).build();
+    assert_eq!(drops.get(), 2);
+    drop(instance);
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+pub fn builder_size_demo() {
+    use scones::{Missing, Present};
+    use std::mem::size_of;
+
+    assert_eq!(
+        size_of::<BasicBuiltBuilder<Missing, Missing>>(),
+        size_of::<BasicBuilt>()
+    );
+    assert_eq!(
+        size_of::<BasicBuiltBuilder<Present, Missing>>(),
+        size_of::<BasicBuilt>()
+    );
+    assert_eq!(
+        size_of::<BasicBuiltBuilder<Present, Present>>(),
+        size_of::<BasicBuilt>()
+    );
+    assert_eq!(
+        size_of::<DropTrackingBuilder<Present, Present>>(),
+        size_of::<DropTracking>()
+    );
+    assert_eq!(
+        size_of::<TemplatedBuilder<u64, Present>>(),
+        size_of::<Templated<u64>>()
+    );
+}
+
+/// An example showing that structs with lifetimes can have constructors and builders.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder]
+/// #[make_constructor]
+/// pub struct Borrowed<'a, 'b: 'a, T: ?Sized + 'b> {
+///     pub name: &'a str,
+///     pub data: &'b T,
+/// }
+/// ```
+#[make_builder]
+#[make_constructor]
+pub struct Borrowed<'a, 'b: 'a, T: ?Sized + 'b> {
+    pub name: &'a str,
+    pub data: &'b T,
+}
+
+/// An example showing that type and const parameters can have defaults.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((fill: Option<T>))]
+/// #[make_constructor(pub new(fill: T))]
+/// pub struct Defaulted<T: Copy + Default = u8, const N: usize = 4> {
+///     #[value([fill; N])]
+///     #[value([fill.unwrap_or_default(); N] for DefaultedBuilder)]
+///     pub data: [T; N],
+/// }
+/// ```
+#[make_builder((fill: Option<T>))]
+#[make_constructor(pub new(fill: T))]
+pub struct Defaulted<T: Copy + Default = u8, const N: usize = 4> {
+    #[value([fill; N])]
+    #[value([fill.unwrap_or_default(); N] for DefaultedBuilder)]
+    pub data: [T; N],
+}
+
+/// An example showing that generic parameters do not have to be used by any builder parameter.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder]
+/// #[make_constructor]
+/// pub struct Unused<'a, T: Default, const N: usize> where T: 'a {
+///     #[value(T::default())]
+///     pub data: T,
+///     #[value(&[])]
+///     pub slice: &'a [u8],
+///     #[value(N)]
+///     pub n: usize,
+/// }
+/// ```
+#[make_builder]
+#[make_constructor]
+pub struct Unused<'a, T: Default, const N: usize>
+where
+    T: 'a,
+{
+    #[value(T::default())]
+    pub data: T,
+    #[value(&[])]
+    pub slice: &'a [u8],
+    #[value(N)]
+    pub n: usize,
+}
+
+/// An example showing that higher-ranked bounds in where clauses are supported.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder]
+/// #[make_constructor((callback) -> Result<Self, ()>)]
+/// pub struct Callback<F, const LIMIT: usize = 10>
+/// where
+///     F: for<'x> Fn(&'x str) -> usize,
+/// {
+///     pub callback: F,
+/// }
+/// ```
+#[make_builder]
+#[make_constructor((callback) -> Result<Self, ()>)]
+pub struct Callback<F, const LIMIT: usize = 10>
+where
+    F: for<'x> Fn(&'x str) -> usize,
+{
+    pub callback: F,
+}
+
+#[test]
+pub fn generics_demo() {
+    let value = 12;
+    let instance = Borrowed::new("twelve", &value);
+    assert_eq!(*instance.data, 12);
+    let instance: Borrowed<str> = BorrowedBuilder::new().data("abc").name("text").build();
+    assert_eq!(instance.data, "abc");
+
+    let instance: Defaulted = Defaulted::new(3);
+    assert_eq!(instance.data, [3; 4]);
+    let instance = Defaulted::<u16, 2>::new(300);
+    assert_eq!(instance.data, [300, 300]);
+    let instance: Defaulted = DefaultedBuilder::new().fill(7).build();
+    assert_eq!(instance.data, [7; 4]);
+    let instance: Defaulted<i64, 1> = DefaultedBuilder::new().build();
+    assert_eq!(instance.data, [0]);
+
+    let instance = Unused::<String, 3>::new();
+    assert_eq!(instance.n, 3);
+    let instance: Unused<String, 5> = UnusedBuilder::new().build();
+    assert_eq!(instance.slice.len(), 0);
+
+    let instance = Callback::<_>::new(|s: &str| s.len()).unwrap();
+    assert_eq!((instance.callback)("four"), 4);
+    let instance: Callback<_, 3> = CallbackBuilder::new().callback(|s: &str| s.len()).build();
+    assert_eq!((instance.callback)("six"), 3);
+}
+
+/// An example showing how to give a constructor generic parameters and a where clause of its own.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_constructor(pub from_iter<I: IntoIterator<Item = u8>>(items: I))]
+/// #[make_constructor(pub from_display<D>(value: D, limit: usize) where D: std::fmt::Display)]
+/// pub struct GenericConstructors {
+///     #[value(items.into_iter().collect() for from_iter)]
+///     #[value(value.to_string().bytes().take(limit).collect() for from_display)]
+///     pub data: Vec<u8>,
+/// }
+/// ```
+#[make_constructor(pub from_iter<I: IntoIterator<Item = u8>>(items: I))]
+#[make_constructor(pub from_display<D>(value: D, limit: usize) where D: std::fmt::Display)]
+pub struct GenericConstructors {
+    #[value(items.into_iter().collect() for from_iter)]
+    #[value(value.to_string().bytes().take(limit).collect() for from_display)]
+    pub data: Vec<u8>,
+}
+
+#[test]
+pub fn generic_constructors_demo() {
+    let instance = GenericConstructors::from_iter(vec![1, 2, 3]);
+    assert_eq!(instance.data, [1, 2, 3]);
+    let instance = GenericConstructors::from_display(12345, 3);
+    assert_eq!(instance.data, b"123");
+}
+
+/// An example showing how to use the derive macros instead of the attribute macros.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[derive(Constructor, Builder, Debug, PartialEq)]
+/// #[constructor]
+/// #[constructor(pub new_identical(shared: i32))]
+/// #[builder(pub DerivedBuilder(b?))]
+/// pub struct Derived {
+///     #[value(shared for new_identical)]
+///     pub a: i32,
+///     #[value(shared for new_identical)]
+///     #[value(-1 for DerivedBuilder)]
+///     pub b: i32,
+/// }
+/// ```
+/// Unlike with `#[make_constructor]`, the `#[value()]` attributes stay on the fields, where they
+/// are ignored by everything other than these derive macros.
+#[derive(Constructor, Builder, Debug, PartialEq)]
+#[constructor]
+#[constructor(pub new_identical(shared: i32))]
+#[builder(pub DerivedBuilder(b?))]
+pub struct Derived {
+    #[value(shared for new_identical)]
+    pub a: i32,
+    #[value(shared for new_identical)]
+    #[value(-1 for DerivedBuilder)]
+    pub b: i32,
+}
+
+/// An example showing that the derive macros also work on generic tuple structs.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[derive(Constructor, Builder)]
+/// pub struct DerivedTuple<T>(pub T, #[value(1)] pub u8);
+/// ```
+#[derive(Constructor, Builder)]
+pub struct DerivedTuple<T>(pub T, #[value(1)] pub u8);
+
+#[test]
+pub fn derived_demo() {
+    assert_eq!(Derived::new(1, 2), Derived { a: 1, b: 2 });
+    assert_eq!(Derived::new_identical(3), Derived { a: 3, b: 3 });
+    assert_eq!(DerivedBuilder::new().a(4).build(), Derived { a: 4, b: -1 });
+    assert_eq!(
+        DerivedBuilder::new().b(5).a(4).build(),
+        Derived { a: 4, b: 5 }
+    );
+
+    let instance = DerivedTuple::new("text");
+    assert_eq!((instance.0, instance.1), ("text", 1));
+    let instance = DerivedTupleBuilder::new().field_0(2.5).build();
+    assert_eq!((instance.0, instance.1), (2.5, 1));
+}
+
+/// An example showing how to give a function named and optional arguments.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((port?, timeout_ms?))]
+/// pub fn connect(
+///     host: &str,
+///     #[value(80)] port: u16,
+///     #[value(1000)] timeout_ms: u32,
+/// ) -> String {
+///     format!("{}:{} ({}ms)", host, port, timeout_ms)
+/// }
+/// ```
+/// This also generates `connect_builder()`, which starts a `ConnectBuilder`.
+#[make_builder((port?, timeout_ms?))]
+pub fn connect(host: &str, #[value(80)] port: u16, #[value(1000)] timeout_ms: u32) -> String {
+    format!("{}:{} ({}ms)", host, port, timeout_ms)
+}
+
+/// An example showing a function builder whose arguments are generic.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder]
+/// pub fn join<T: ToString>(items: &[T], separator: impl AsRef<str>) -> String {
+///     let items: Vec<_> = items.iter().map(ToString::to_string).collect();
+///     items.join(separator.as_ref())
+/// }
+/// ```
+/// Like with generic structs, the setter for `separator` can change the type of the argument.
+#[make_builder]
+pub fn join<T: ToString>(items: &[T], separator: impl AsRef<str>) -> String {
+    let items: Vec<_> = items.iter().map(ToString::to_string).collect();
+    items.join(separator.as_ref())
+}
+
+/// An example showing how to make builders for methods.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// pub struct Server {
+///     pub name: String,
+/// }
+///
+/// #[make_builder]
+/// impl Server {
+///     #[make_builder((greeting?))]
+///     pub fn greet(&self, #[value(Self::default_greeting())] greeting: &str, name: &str) -> String {
+///         format!("{} {}, this is {}.", greeting, name, self.name)
+///     }
+///
+///     #[make_builder((name?))]
+///     /// ^ Builds a `Server`, named "server" by default.
+///     pub fn create(#[value("server".to_owned())] name: String) -> Self {
+///         Self { name }
+///     }
+///
+///     fn default_greeting() -> &'static str {
+///         "Hello"
+///     }
+/// }
+/// ```
+/// Putting `#[make_builder]` on the impl block adds `greet_builder(&self)` and `create_builder()`.
+pub struct Server {
+    pub name: String,
+}
+
+#[make_builder]
+impl Server {
+    #[make_builder((greeting?))]
+    pub fn greet(&self, #[value(Self::default_greeting())] greeting: &str, name: &str) -> String {
+        format!("{} {}, this is {}.", greeting, name, self.name)
+    }
+
+    #[make_builder((name?))]
+    /// ^ Builds a `Server`, named "server" by default.
+    pub fn create(#[value("server".to_owned())] name: String) -> Self {
+        Self { name }
+    }
+
+    fn default_greeting() -> &'static str {
+        "Hello"
+    }
+}
+
+#[test]
+pub fn function_builder_demo() {
+    assert_eq!(connect_builder().host("a").call(), "a:80 (1000ms)");
+    assert_eq!(
+        ConnectBuilder::new().timeout_ms(5).host("b").port(8).call(),
+        "b:8 (5ms)"
+    );
+    assert_eq!(connect("c", 1, 2), "c:1 (2ms)");
+
+    assert_eq!(
+        join_builder().items(&[1, 2, 3]).separator(", ").call(),
+        "1, 2, 3"
+    );
+    let separator = String::from("-");
+    assert_eq!(
+        join_builder()
+            .separator(&separator)
+            .items(&['a', 'b'])
+            .call(),
+        "a-b"
+    );
+
+    let server = Server::create_builder().call();
+    assert_eq!(server.name, "server");
+    let server = Server::create_builder().name("Rex".to_owned()).call();
+    assert_eq!(
+        server.greet_builder().name("Sam").call(),
+        "Hello Sam, this is Rex."
+    );
+    assert_eq!(
+        server.greet_builder().greeting("Hi").name("Sam").call(),
+        "Hi Sam, this is Rex."
+    );
+}
+
+/// An example showing how to generate accessors alongside constructors.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_constructor]
+/// #[make_accessors((get, set, with))]
+/// #[make_accessors(pub(crate) (get_mut))]
+/// pub struct Account {
+///     id: u32,
+///     #[accessors(pub(get, get_mut, with))]
+///     owner: String,
+///     #[accessors((get))]
+///     balance: i64,
+///     #[accessors(skip)]
+///     pub history: Vec<i64>,
+/// }
+/// ```
+/// `owner_mut()` is public because the visibility on the field replaces the one given to
+/// `#[make_accessors]`, while `id_mut()` is only visible inside this crate.
+#[make_constructor]
+#[make_accessors((get, set, with))]
+#[make_accessors(pub(crate) (get_mut))]
+pub struct Account {
+    id: u32,
+    #[accessors(pub(get, get_mut, with))]
+    owner: String,
+    #[accessors((get))]
+    balance: i64,
+    #[accessors(skip)]
+    pub history: Vec<i64>,
+}
+
+/// An example showing accessors for a generic tuple struct.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_accessors]
+/// pub struct Pair<T>(T, #[accessors(copy)] Option<u8>);
+/// ```
+#[make_accessors]
+pub struct Pair<T>(T, #[accessors(copy)] Option<u8>);
+
+#[test]
+pub fn accessors_demo() {
+    let mut account = Account::new(1, "Sam".to_owned(), 10, Vec::new());
+    assert_eq!(account.id(), 1);
+    assert_eq!(account.owner(), "Sam");
+    assert_eq!(account.balance(), 10);
+    account.owner_mut().push_str("ia");
+    account.set_id(2);
+    let account = account.with_owner(format!("{}!", "Sam"));
+    assert_eq!((account.id(), &account.owner[..]), (2, "Sam!"));
+    assert!(account.history.is_empty());
+
+    let pair = Pair(vec![1], Some(3)).with_field_0(vec![2]);
+    assert_eq!(pair.field_0(), &vec![2]);
+    assert_eq!(pair.field_1(), Some(3));
+}
+
+/// An example showing how to generate a function which takes a struct apart again.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_constructor(pub new(.., label) => pub into_parts)]
+/// #[make_constructor(pub new_unlabeled)]
+/// pub struct Labeled<T> {
+///     #[value("" for new_unlabeled)]
+///     label: &'static str,
+///     value: T,
+/// }
+/// ```
+/// `into_parts()` returns `(value, label)`, matching the order of the parameters of `new()`.
+#[make_constructor(pub new(.., label) => pub into_parts)]
+#[make_constructor(pub new_unlabeled)]
+pub struct Labeled<T> {
+    #[value("" for new_unlabeled)]
+    label: &'static str,
+    value: T,
+}
+
+#[test]
+pub fn inverse_demo() {
+    let labeled = Labeled::new(vec![1, 2], "list");
+    assert_eq!(labeled.into_parts(), (vec![1, 2], "list"));
+    assert_eq!(Labeled::new_unlabeled(3).into_parts(), (3, ""));
+}
+
+/// An example showing how to flatten the parameters of a field's constructor into the
+/// constructors and builders of the struct containing it.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// # #[make_constructor]
+/// # pub struct Point { pub x: i32, pub y: i32 }
+/// #[make_constructor]
+/// #[make_constructor(pub at_origin)]
+/// #[make_builder((size?))]
+/// pub struct Sprite {
+///     pub name: &'static str,
+///     #[flatten(new(x: i32, y: i32))]
+///     #[value(Point::new(0, 0) for at_origin)]
+///     pub position: Point,
+///     #[value(1)]
+///     pub size: u32,
+/// }
+/// ```
+/// `Point` has a constructor `new(x: i32, y: i32)`.
+#[make_constructor]
+#[make_constructor(pub at_origin)]
+#[make_builder((size?))]
+pub struct Sprite {
+    pub name: &'static str,
+    #[flatten(new(x: i32, y: i32))]
+    #[value(Point::new(0, 0) for at_origin)]
+    pub position: Point,
+    #[value(1)]
+    pub size: u32,
+}
+
+/// The position of a [`Sprite`].
+#[make_constructor]
+#[derive(Debug, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[test]
+pub fn flatten_demo() {
+    let sprite = Sprite::new("a", 1, 2);
+    assert_eq!(
+        (sprite.name, sprite.position, sprite.size),
+        ("a", Point::new(1, 2), 1)
+    );
+    let sprite = Sprite::at_origin("b");
+    assert_eq!(sprite.position, Point::new(0, 0));
+    let sprite = SpriteBuilder::new().y(4).name("c").x(3).size(2).build();
+    assert_eq!((sprite.position, sprite.size), (Point::new(3, 4), 2));
+}
+
+/// An example showing constructors which forward to other constructors.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_constructor(pub with_size)]
+/// #[make_constructor(pub unit_square = with_size(1.0, 1.0))]
+/// #[make_constructor(pub square(size: f32) = with_size(size, size))]
+/// #[make_constructor(pub try_square(size: f32) -> Result<Self, String> = checked(size, size)?)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct Rectangle {
+///     pub width: f32,
+///     pub height: f32,
+/// }
+///
+/// impl Rectangle {
+///     pub fn checked(width: f32, height: f32) -> Result<Self, &'static str> {
+///         if width >= 0.0 && height >= 0.0 {
+///             Ok(Self::with_size(width, height))
+///         } else {
+///             Err("Sizes cannot be negative.")
+///         }
+///     }
+/// }
+/// ```
+#[make_constructor(pub with_size)]
+#[make_constructor(pub unit_square = with_size(1.0, 1.0))]
+#[make_constructor(pub square(size: f32) = with_size(size, size))]
+#[make_constructor(pub try_square(size: f32) -> Result<Self, String> = checked(size, size)?)]
+#[derive(Debug, PartialEq)]
+pub struct Rectangle {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rectangle {
+    pub fn checked(width: f32, height: f32) -> Result<Self, &'static str> {
+        if width >= 0.0 && height >= 0.0 {
+            Ok(Self::with_size(width, height))
+        } else {
+            Err("Sizes cannot be negative.")
+        }
+    }
+}
+
+#[test]
+pub fn delegate_demo() {
+    assert_eq!(Rectangle::unit_square(), Rectangle::with_size(1.0, 1.0));
+    assert_eq!(Rectangle::square(2.0), Rectangle::with_size(2.0, 2.0));
+    assert_eq!(
+        Rectangle::try_square(3.0),
+        Ok(Rectangle::with_size(3.0, 3.0))
+    );
+    assert_eq!(
+        Rectangle::try_square(-1.0),
+        Err("Sizes cannot be negative.".to_owned())
+    );
+}
+
+/// The error returned when a [`HostName`] would be empty.
+#[derive(Debug, PartialEq)]
+pub struct EmptyName;
+
+fn check_not_empty(name: &str) -> Result<(), EmptyName> {
+    if name.is_empty() {
+        Err(EmptyName)
+    } else {
+        Ok(())
+    }
+}
+
+/// An example showing how to make a validated newtype.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// # #[derive(Debug, PartialEq)]
+/// # pub struct EmptyName;
+/// # fn check_not_empty(name: &str) -> Result<(), EmptyName> { Ok(()) }
+/// #[make_newtype(validate = check_not_empty, error = EmptyName, deref, display, from_str)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct HostName(String);
+/// ```
+#[make_newtype(validate = check_not_empty, error = EmptyName, deref, display, from_str)]
+#[derive(Debug, PartialEq)]
+pub struct HostName(String);
+
+/// An example showing a newtype without validation, which wraps a generic value.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_newtype(display, from_str)]
+/// pub struct Meters<T>(T);
+/// ```
+#[make_newtype(display, from_str)]
+pub struct Meters<T>(T);
+
+/// The error returned when a [`Positive`] would not be greater than zero.
+#[derive(Debug, PartialEq)]
+pub struct NotPositive;
+
+fn check_positive<T: PartialOrd + Default>(value: &T) -> Result<(), NotPositive> {
+    if *value > T::default() {
+        Ok(())
+    } else {
+        Err(NotPositive)
+    }
+}
+
+/// An example showing a validated newtype which wraps a generic value.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// # #[derive(Debug, PartialEq)]
+/// # pub struct NotPositive;
+/// # fn check_positive<T>(value: &T) -> Result<(), NotPositive> { Ok(()) }
+/// #[make_newtype(validate = check_positive, error = NotPositive, from_str)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct Positive<T: PartialOrd + Default>(T);
+/// ```
+#[make_newtype(validate = check_positive, error = NotPositive, from_str)]
+#[derive(Debug, PartialEq)]
+pub struct Positive<T: PartialOrd + Default>(T);
+
+#[test]
+pub fn newtype_demo() {
+    use scones::ParseNewtypeError;
+    use std::convert::TryFrom;
+
+    let name = HostName::try_new("localhost".to_owned()).unwrap();
+    assert_eq!(name.len(), 9);
+    assert_eq!(name.to_string(), "localhost");
+    assert_eq!(name.as_ref(), "localhost");
+    assert_eq!(HostName::try_from(String::new()), Err(EmptyName));
+    assert_eq!(
+        "".parse::<HostName>(),
+        Err(ParseNewtypeError::Invalid(EmptyName))
+    );
+    assert_eq!("a".parse::<HostName>().unwrap().into_inner(), "a");
+    let unchecked = unsafe { HostName::new_unchecked("b".to_owned()) };
+    assert_eq!(unchecked.into_inner(), "b");
+
+    let distance = Meters::new(3);
+    assert_eq!(distance.to_string(), "3");
+    let distance: Meters<f32> = "2.5".parse().unwrap();
+    assert_eq!(distance.into_inner(), 2.5);
+    assert!("x".parse::<Meters<u8>>().is_err());
+    assert_eq!(*Meters::from('a').as_ref(), 'a');
+
+    assert_eq!(Positive::try_new(3).unwrap().into_inner(), 3);
+    assert_eq!(Positive::try_new(-0.5), Err(NotPositive));
+    let amount: Positive<u64> = "12".parse().unwrap();
+    assert_eq!(*amount.as_ref(), 12);
+    assert_eq!(
+        "0".parse::<Positive<i8>>(),
+        Err(ParseNewtypeError::Invalid(NotPositive))
+    );
+}
+
+fn is_lowercase(text: &str) -> bool {
+    text.chars().all(|c| !c.is_uppercase())
+}
+
+/// An example showing constraints on the values of fields.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// # fn is_lowercase(text: &str) -> bool { true }
+/// #[make_constructor]
+/// #[make_constructor(pub try_new -> Result<Self, Box<dyn std::error::Error>>)]
+/// #[make_builder((retries?) -> Result<Self, UserCheckError>)]
+/// #[derive(Debug)]
+/// pub struct User {
+///     #[check(non_empty, len < 16, fn = is_lowercase)]
+///     pub name: String,
+///     #[check(range = 0..5)]
+///     #[value(3)]
+///     pub retries: u8,
+/// }
+/// ```
+/// This generates `UserCheckError`, with the variants `NameNonEmpty`, `NameLen`, `NameIsLowercase`
+/// and `RetriesRange`.
+#[make_constructor]
+#[make_constructor(pub try_new -> Result<Self, Box<dyn std::error::Error>>)]
+#[make_builder((retries?) -> Result<Self, UserCheckError>)]
+#[derive(Debug)]
+pub struct User {
+    #[check(non_empty, len < 16, fn = is_lowercase)]
+    pub name: String,
+    #[check(range = 0..5)]
+    #[value(3)]
+    pub retries: u8,
+}
+
+#[test]
+pub fn checks_demo() {
+    assert_eq!(User::new("sam".to_owned()).retries, 3);
+    assert_eq!(
+        User::try_new("Sam".to_owned()).unwrap_err().to_string(),
+        "name was rejected by is_lowercase."
+    );
+    assert_eq!(
+        User::try_new("a".repeat(16)).unwrap_err().to_string(),
+        UserCheckError::NameLen.to_string()
+    );
+    let result = UserBuilder::new().name("sam".to_owned()).retries(5).build();
+    assert_eq!(result.unwrap_err(), UserCheckError::RetriesRange);
+    let result = UserBuilder::new().retries(4).name("sam".to_owned()).build();
+    assert_eq!(result.unwrap().retries, 4);
+}
+
+#[test]
+#[should_panic(expected = "name must not be empty.")]
+pub fn checks_panic_demo() {
+    User::new(String::new());
+}
+
+/// The error type used by `Listener`.
+#[derive(Debug, PartialEq)]
+pub enum ListenerError {
+    Parse(std::num::ParseIntError),
+    Check(ListenerCheckError),
+}
+
+impl From<std::num::ParseIntError> for ListenerError {
+    fn from(error: std::num::ParseIntError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<ListenerCheckError> for ListenerError {
+    fn from(error: ListenerCheckError) -> Self {
+        Self::Check(error)
+    }
+}
+
+/// An example showing a constructor which reports the errors of every field at once.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// # #[derive(Debug)] pub enum ListenerError {}
+/// # impl From<std::num::ParseIntError> for ListenerError { fn from(_: std::num::ParseIntError) -> Self { todo!() } }
+/// # impl From<ListenerCheckError> for ListenerError { fn from(_: ListenerCheckError) -> Self { todo!() } }
+/// #[make_constructor(pub parse(host: &str, port: &str) -> Result<Self, scones::Errors<ListenerError>>)]
+/// #[derive(Debug)]
+/// pub struct Listener {
+///     #[check(non_empty)]
+///     #[value(host.to_owned())]
+///     pub host: String,
+///     #[check(range = 1..)]
+///     #[value(port.parse()?)]
+///     pub port: u16,
+/// }
+/// ```
+#[make_constructor(pub parse(host: &str, port: &str) -> Result<Self, scones::Errors<ListenerError>>)]
+#[derive(Debug)]
+pub struct Listener {
+    #[check(non_empty)]
+    #[value(host.to_owned())]
+    pub host: String,
+    #[check(range = 1..)]
+    #[value(port.parse()?)]
+    pub port: u16,
+}
+
+/// An example showing a tuple struct whose builder reports the errors of every field at once.
+#[make_builder((a: String, b: String) -> Result<Self, ::scones::Errors<std::num::ParseFloatError>>)]
+pub struct Coordinates(#[value(a.parse()?)] pub f64, #[value(b.parse()?)] pub f64);
+
+#[test]
+pub fn accumulate_demo() {
+    let listener = Listener::parse("localhost", "8080").unwrap();
+    assert_eq!(listener.port, 8080);
+
+    let errors = Listener::parse("", "0").unwrap_err();
+    let errors: Vec<_> = errors.into_iter().collect();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].field(), "host");
+    assert_eq!(
+        errors[0].error(),
+        &ListenerError::Check(ListenerCheckError::HostNonEmpty)
+    );
+    assert_eq!(errors[1].field(), "port");
+
+    // Parsing failed, so the range check on port is never run.
+    let errors = Listener::parse("", "port").unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors.iter().last().unwrap().error(),
+        ListenerError::Parse(_)
+    ));
+
+    let builder = CoordinatesBuilder::new().a("x".to_owned());
+    let errors = builder.b("y".to_owned()).build().err().unwrap();
+    assert_eq!(
+        errors.to_string(),
+        "0: invalid float literal\n1: invalid float literal"
+    );
+    let builder = CoordinatesBuilder::new().a("1.5".to_owned());
+    let coordinates = builder.b("2".to_owned()).build().unwrap();
+    assert_eq!((coordinates.0, coordinates.1), (1.5, 2.0));
+}
+
+/// An example showing a struct which is deserialized through its builder.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((port?, workers: Option<usize>), serde)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct ServiceConfig {
+///     pub name: String,
+///     #[value(8080)]
+///     pub port: u16,
+///     #[value(workers.unwrap_or(4) * 2)]
+///     pub threads: usize,
+/// }
+/// ```
+/// The name is required, while `port` and `workers` can be left out of the input.
+#[make_builder((port?, workers: Option<usize>), serde)]
+#[derive(Debug, PartialEq)]
+pub struct ServiceConfig {
+    pub name: String,
+    #[value(8080)]
+    pub port: u16,
+    #[value(workers.unwrap_or(4) * 2)]
+    pub threads: usize,
+}
+
+/// An example showing a fallible builder which is used for deserialization.
+#[make_builder(-> Result<Self, EndpointCheckError>, serde)]
+#[derive(Debug)]
+pub struct Endpoint<T> {
+    #[check(non_empty)]
+    pub path: String,
+    pub payload: T,
+}
+
+#[test]
+pub fn serde_demo() {
+    let config: ServiceConfig = serde_json::from_str(r#"{ "name": "api" }"#).unwrap();
+    assert_eq!(
+        config,
+        ServiceConfigBuilder::new().name("api".to_owned()).build()
+    );
+    assert_eq!((config.port, config.threads), (8080, 8));
+
+    let json = r#"{ "name": "api", "port": 80, "workers": 1 }"#;
+    let config: ServiceConfig = serde_json::from_str(json).unwrap();
+    assert_eq!((config.port, config.threads), (80, 2));
+
+    let error = serde_json::from_str::<ServiceConfig>("{}").unwrap_err();
+    assert!(error.to_string().starts_with("missing fields `name`"));
+    let json = r#"{ "name": "api", "threads": 3 }"#;
+    let error = serde_json::from_str::<ServiceConfig>(json).unwrap_err();
+    assert!(error.to_string().starts_with("unknown field `threads`"));
+
+    let json = r#"{ "path": "/users", "payload": [1, 2] }"#;
+    let endpoint: Endpoint<Vec<i32>> = serde_json::from_str(json).unwrap();
+    assert_eq!(endpoint.payload, [1, 2]);
+    let json = r#"{ "path": "", "payload": "data" }"#;
+    let error = serde_json::from_str::<Endpoint<String>>(json).unwrap_err();
+    assert!(error.to_string().starts_with("path must not be empty."));
+}
+
+/// Holds an example of partial values, so that `partial_demo` shows they can be used from outside
+/// the module which defines them.
+pub mod daemon {
+    use scones::make_builder;
+
+    /// An example showing layered configuration built from partial values.
+    ///
+    /// It is defined as follows:
+    /// ```
+    /// # use scones::*;
+    /// #[make_builder((host?, port?, verbose: Option<bool>) -> Result<Self, DaemonCheckError>, partial)]
+    /// pub struct Daemon<T> {
+    ///     #[value("localhost".to_owned())]
+    ///     pub host: String,
+    ///     #[check(range = 1..)]
+    ///     #[value(8080)]
+    ///     pub port: u16,
+    ///     #[value(verbose.unwrap_or(false))]
+    ///     pub verbose: bool,
+    ///     pub state: T,
+    /// }
+    /// ```
+    #[make_builder((host?, port?, verbose: Option<bool>) -> Result<Self, DaemonCheckError>, partial)]
+    pub struct Daemon<T> {
+        #[value("localhost".to_owned())]
+        pub host: String,
+        #[check(range = 1..)]
+        #[value(8080)]
+        pub port: u16,
+        #[value(verbose.unwrap_or(false))]
+        pub verbose: bool,
+        pub state: T,
+    }
+}
+
+#[test]
+pub fn partial_demo() {
+    use daemon::{DaemonCheckError, DaemonPartial};
+
+    let defaults = DaemonPartial::new().state(0u8).verbose(false);
+    let file = DaemonPartial {
+        port: Some(9000),
+        host: Some("example.com".to_owned()),
+        ..DaemonPartial::default()
+    };
+    let flags = DaemonPartial::new().verbose(true);
+    let daemon = defaults.merge(file).merge(flags).build().unwrap();
+    assert_eq!(&daemon.host, "example.com");
+    assert_eq!((daemon.port, daemon.verbose, daemon.state), (9000, true, 0));
+
+    let daemon = DaemonPartial::new().state("idle").build().unwrap();
+    assert_eq!((&daemon.host[..], daemon.port), ("localhost", 8080));
+
+    let error = DaemonPartial::<()>::new().build().err().unwrap();
+    assert_eq!(error, scones::BuildError::Missing(vec!["state"]));
+    let error = DaemonPartial::new()
+        .state(())
+        .port(0)
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(
+        error,
+        scones::BuildError::Failed(DaemonCheckError::PortRange)
+    );
+}
+
+/// An example showing values which can be read from environment variables.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((level?, color?))]
+/// pub struct Logger {
+///     pub target: String,
+///     #[env("SCONES_EXAMPLE_LOG_LEVEL")]
+///     #[value(3)]
+///     pub level: u8,
+///     #[env("SCONES_EXAMPLE_LOG_COLOR")]
+///     #[value(false)]
+///     pub color: bool,
+/// }
+/// ```
+#[make_builder((level?, color?))]
+pub struct Logger {
+    pub target: String,
+    #[env("SCONES_EXAMPLE_LOG_LEVEL")]
+    #[value(3)]
+    pub level: u8,
+    #[env("SCONES_EXAMPLE_LOG_COLOR")]
+    #[value(false)]
+    pub color: bool,
+}
+
+/// Environment variables are shared by every test in the process, so the tests which set them take
+/// turns. The variables are removed before and after each one.
+#[cfg(test)]
+fn with_env_vars(names: &[&str], test: impl FnOnce()) {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    let _guard = LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    let clear = || {
+        for name in names {
+            std::env::remove_var(name);
+        }
+    };
+    clear();
+    test();
+    clear();
+}
+
+#[test]
+pub fn env_demo() {
+    let names = ["SCONES_EXAMPLE_LOG_LEVEL", "SCONES_EXAMPLE_LOG_COLOR"];
+    with_env_vars(&names, || {
+        let builder = LoggerBuilder::new().level(1);
+        let logger = builder
+            .env_overrides()
+            .unwrap()
+            .target("app".to_owned())
+            .build();
+        assert_eq!((logger.level, logger.color), (1, false));
+
+        std::env::set_var("SCONES_EXAMPLE_LOG_LEVEL", "5");
+        std::env::set_var("SCONES_EXAMPLE_LOG_COLOR", "true");
+        let builder = LoggerBuilder::new().level(1).env_overrides().unwrap();
+        let logger = builder.target("app".to_owned()).build();
+        assert_eq!((logger.level, logger.color), (5, true));
+
+        std::env::set_var("SCONES_EXAMPLE_LOG_LEVEL", "-1");
+        std::env::set_var("SCONES_EXAMPLE_LOG_COLOR", "yes");
+        let errors = LoggerBuilder::new().env_overrides().err().unwrap();
+        let fields: Vec<_> = errors.iter().map(|error| error.field()).collect();
+        assert_eq!(fields, ["level", "color"]);
+        let first = errors.iter().next().unwrap().error();
+        assert_eq!(first.variable(), "SCONES_EXAMPLE_LOG_LEVEL");
+        assert_eq!(first.message(), "invalid digit found in string");
+    });
+}
+
+/// An example showing a builder which reads every parameter from environment variables, including
+/// required ones, through its partial value.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder(partial)]
+/// pub struct Worker {
+///     #[env("SCONES_EXAMPLE_WORKER_QUEUE")]
+///     pub queue: String,
+///     #[env("SCONES_EXAMPLE_WORKER_THREADS")]
+///     pub threads: usize,
+/// }
+/// ```
+#[make_builder(partial)]
+pub struct Worker {
+    #[env("SCONES_EXAMPLE_WORKER_QUEUE")]
+    pub queue: String,
+    #[env("SCONES_EXAMPLE_WORKER_THREADS")]
+    pub threads: usize,
+}
+
+#[test]
+pub fn builder_from_env_demo() {
+    let names = [
+        "SCONES_EXAMPLE_WORKER_QUEUE",
+        "SCONES_EXAMPLE_WORKER_THREADS",
+    ];
+    with_env_vars(&names, || {
+        std::env::set_var("SCONES_EXAMPLE_WORKER_QUEUE", "jobs");
+        let partial: WorkerPartial = WorkerBuilder::from_env().unwrap();
+        let error = partial.build().err().unwrap();
+        assert_eq!(error, scones::BuildError::Missing(vec!["threads"]));
+
+        std::env::set_var("SCONES_EXAMPLE_WORKER_THREADS", "4");
+        let worker = WorkerBuilder::from_env().unwrap().build().unwrap();
+        assert_eq!((&worker.queue[..], worker.threads), ("jobs", 4));
+
+        std::env::set_var("SCONES_EXAMPLE_WORKER_THREADS", "four");
+        let errors = WorkerBuilder::from_env().err().unwrap();
+        assert_eq!(errors.iter().next().unwrap().field(), "threads");
+    });
+}
+
+/// An example showing a struct which can be created from command-line arguments.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((port?, verbose?, threads: Option<usize>), cli)]
+/// pub struct Serve {
+///     /// The directory to serve files from.
+///     pub root: String,
+///     /// The port to listen on.
+///     #[value(8080)]
+///     pub port: u16,
+///     /// Print every request.
+///     #[value(false)]
+///     pub verbose: bool,
+///     #[value(threads.unwrap_or(1))]
+///     pub max_threads: usize,
+/// }
+/// ```
+#[make_builder((port?, verbose?, threads: Option<usize>), cli)]
+pub struct Serve {
+    /// The directory to serve files from.
+    pub root: String,
+    /// The port to listen on.
+    #[value(8080)]
+    pub port: u16,
+    /// Print every request.
+    #[value(false)]
+    pub verbose: bool,
+    #[value(threads.unwrap_or(1))]
+    pub max_threads: usize,
+}
+
+#[test]
+pub fn cli_demo() {
+    use scones::CliError;
+
+    let serve = Serve::from_args(vec!["--root", "public"]).unwrap();
+    assert_eq!(
+        (&serve.root[..], serve.port, serve.verbose),
+        ("public", 8080, false)
+    );
+    let args = vec!["--port=80", "--verbose", "--root", "www", "--threads", "4"];
+    let serve = Serve::from_args(args).unwrap();
+    assert_eq!(
+        (&serve.root[..], serve.port, serve.verbose),
+        ("www", 80, true)
+    );
+    assert_eq!(serve.max_threads, 4);
+    let serve = Serve::from_args(vec!["--root", "www", "--verbose", "--no-verbose"]).unwrap();
+    assert!(!serve.verbose);
+
+    let error = Serve::from_args(Vec::<String>::new()).err().unwrap();
+    assert_eq!(error, CliError::Missing(vec!["--root".to_owned()]));
+    let error = Serve::from_args(vec!["--root", "a", "--port", "http"]).err();
+    assert!(matches!(error, Some(CliError::Invalid { .. })));
+    let error = Serve::from_args(vec!["--root"]).err();
+    assert_eq!(error, Some(CliError::MissingValue("--root".to_owned())));
+    let error = Serve::from_args(vec!["extra"]).err();
+    assert_eq!(error, Some(CliError::Unknown("extra".to_owned())));
+    let error = Serve::from_args(vec!["--root", "a", "--no-port"]).err();
+    assert_eq!(error, Some(CliError::Unknown("--no-port".to_owned())));
+
+    let help = Serve::cli_help();
+    assert_eq!(
+        Serve::from_args(vec!["-h"]).err(),
+        Some(CliError::Help(help.clone()))
+    );
+    assert!(help.contains("--root <String>"));
+    assert!(help.contains("The directory to serve files from. [required]"));
+    assert!(help.contains("The port to listen on. [default: 8080]"));
+    assert!(help.contains("--[no-]verbose "));
+    assert!(help.contains("--threads <usize>"));
+}
+
+/// An example showing the descriptions of constructors and builders which are available at
+/// runtime through `scones::Describe`.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_constructor]
+/// #[make_constructor(pub square(size: f32))]
+/// #[make_builder((label?, ratio: Option<f32>))]
+/// #[make_describe]
+/// pub struct Shape {
+///     /// The width of the shape.
+///     #[value(size for square)]
+///     pub width: f32,
+///     /// The height of the shape.
+///     ///
+///     /// It does not have to match the width.
+///     #[value(size for square)]
+///     #[value(width * ratio.unwrap_or(1.0) for ShapeBuilder)]
+///     pub height: f32,
+///     #[value("shape".to_owned())]
+///     pub label: String,
+///     #[value(Vec::new())]
+///     pub tags: Vec<&'static str>,
+/// }
+/// ```
+#[make_constructor]
+#[make_constructor(pub square(size: f32))]
+#[make_builder((label?, ratio: Option<f32>))]
+#[make_describe]
+pub struct Shape {
+    /// The width of the shape.
+    #[value(size for square)]
+    pub width: f32,
+    /// The height of the shape.
+    ///
+    /// It does not have to match the width.
+    #[value(size for square)]
+    #[value(width * ratio.unwrap_or(1.0) for ShapeBuilder)]
+    pub height: f32,
+    #[value("shape".to_owned())]
+    pub label: String,
+    #[value(Vec::new())]
+    pub tags: Vec<&'static str>,
+}
+
+#[test]
+pub fn describe_demo() {
+    use scones::{Describe, ParamKind};
+
+    let description = Shape::describe();
+    assert_eq!(description.name, "Shape");
+    let field_names: Vec<_> = description.fields.iter().map(|f| f.name).collect();
+    assert_eq!(field_names, ["width", "height", "label", "tags"]);
+    let height = description.field("height").unwrap();
+    assert_eq!(
+        height.doc,
+        "The height of the shape.\n\nIt does not have to match the width."
+    );
+    assert_eq!(height.default, None);
+    let label = description.field("label").unwrap();
+    assert_eq!(label.default, Some("\"shape\".to_owned()"));
+    assert_eq!(
+        description.field("tags").unwrap().type_name,
+        "Vec<&'static str>"
+    );
+
+    let new = description.constructor("new").unwrap();
+    let params: Vec<_> = new.params.iter().map(|p| (p.name, p.kind)).collect();
+    assert_eq!(
+        params,
+        [
+            ("width", ParamKind::Required),
+            ("height", ParamKind::Required)
+        ]
+    );
+    let square = description.constructor("square").unwrap();
+    assert_eq!(square.params.len(), 1);
+    assert_eq!(square.params[0].kind, ParamKind::Custom);
+    assert_eq!(square.params[0].type_name, "f32");
+
+    let builder = description.builder("ShapeBuilder").unwrap();
+    let params: Vec<_> = builder.params.iter().map(|p| (p.name, p.kind)).collect();
+    assert_eq!(
+        params,
+        [
+            ("label", ParamKind::Override),
+            ("ratio", ParamKind::Optional),
+            ("width", ParamKind::Required),
+        ]
+    );
+    assert_eq!(
+        builder.param("label").unwrap().default,
+        Some("\"shape\".to_owned()")
+    );
+    assert!(description.builder("OtherBuilder").is_none());
+
+    let shape = ShapeBuilder::new().width(2.0).ratio(1.5).build();
+    assert_eq!((shape.width, shape.height), (2.0, 3.0));
+
+    let description = Gauge::describe();
+    let default = |name| description.field(name).unwrap().default.unwrap();
+    assert_eq!(default("step"), "if LEVELS < 3 { 1 } else { 2 }");
+    // How macro calls are spaced depends on the version of the compiler.
+    assert!(default("offset").ends_with("[1, 2].len() as i32 - 1"));
+    assert_eq!(default("scale"), "| v : i32 | -> i32 { v * - 2 }");
+}
+
+const LEVELS: i32 = 4;
+
+/// An example showing descriptions of more complicated `#[value()]` expressions.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// # const LEVELS: i32 = 4;
+/// #[make_constructor]
+/// #[make_describe]
+/// pub struct Gauge {
+///     #[value(if LEVELS < 3 { 1 } else { 2 })]
+///     pub step: i32,
+///     #[value(vec![1, 2].len() as i32 - 1)]
+///     pub offset: i32,
+///     #[value(|v: i32| -> i32 { v * -2 })]
+///     pub scale: fn(i32) -> i32,
+/// }
+/// ```
+#[make_constructor]
+#[make_describe]
+pub struct Gauge {
+    #[value(if LEVELS < 3 { 1 } else { 2 })]
+    pub step: i32,
+    #[value(vec![1, 2].len() as i32 - 1)]
+    pub offset: i32,
+    #[value(|v: i32| -> i32 { v * -2 })]
+    pub scale: fn(i32) -> i32,
+}
+
+/// An example showing a struct whose parameters can be set by name at runtime.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((retries?, timeout: Option<f64>), dynamic)]
+/// pub struct Plugin {
+///     pub name: String,
+///     #[value(3)]
+///     pub retries: u32,
+///     pub hooks: Vec<String>,
+///     #[value(timeout.unwrap_or(1.5))]
+///     pub timeout: f64,
+/// }
+/// ```
+#[make_builder((retries?, timeout: Option<f64>), dynamic)]
+pub struct Plugin {
+    pub name: String,
+    #[value(3)]
+    pub retries: u32,
+    pub hooks: Vec<String>,
+    #[value(timeout.unwrap_or(1.5))]
+    pub timeout: f64,
+}
+
+#[test]
+pub fn dynamic_demo() {
+    use scones::{BuildError, SetError};
+
+    let mut builder = PluginDynBuilder::new();
+    builder.set_str("name", "audit").unwrap();
+    builder
+        .set("hooks", Box::new(vec!["save".to_owned()]))
+        .unwrap();
+    let plugin = builder.build().unwrap();
+    assert_eq!((&plugin.name[..], plugin.retries), ("audit", 3));
+    assert_eq!(
+        (&plugin.hooks[..], plugin.timeout),
+        (&["save".to_owned()][..], 1.5)
+    );
+
+    let mut builder = PluginDynBuilder::default();
+    builder.set_str("retries", "5").unwrap();
+    builder.set("timeout", Box::new(0.5f64)).unwrap();
+    assert_eq!(
+        builder.build().err(),
+        Some(BuildError::Missing(vec!["name", "hooks"]))
+    );
+
+    let mut builder = PluginDynBuilder::new();
+    assert_eq!(
+        builder.set("retries", Box::new("5")),
+        Err(SetError::WrongType {
+            param: "retries",
+            expected: "u32"
+        })
+    );
+    assert!(matches!(
+        builder.set_str("retries", "many"),
+        Err(SetError::Invalid {
+            param: "retries",
+            ..
+        })
+    ));
+    assert!(matches!(
+        builder.set_str("hooks", "save"),
+        Err(SetError::Invalid { param: "hooks", .. })
+    ));
+    assert_eq!(
+        builder.set_str("color", "red"),
+        Err(SetError::Unknown("color".to_owned()))
+    );
+}
+
+/// An example showing a struct which can describe the input of its builder as a JSON schema.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((port?), schema)]
+/// pub struct DatabaseConfig {
+///     /// The address of the database server.
+///     pub host: String,
+///     #[value(5432)]
+///     pub port: u16,
+/// }
+///
+/// #[make_builder((name?, retries?, timeout: Option<f64>), schema)]
+/// pub struct AppConfig {
+///     /// The name shown in logs.
+///     #[value("app".to_owned())]
+///     pub name: String,
+///     #[value(-1)]
+///     pub retries: i32,
+///     pub database: DatabaseConfig,
+///     pub features: Vec<String>,
+///     #[value(std::time::Duration::from_secs_f64(timeout.unwrap_or(1.0)))]
+///     pub timeout: std::time::Duration,
+/// }
+/// ```
+#[make_builder((port?), schema)]
+pub struct DatabaseConfig {
+    /// The address of the database server.
+    pub host: String,
+    #[value(5432)]
+    pub port: u16,
+}
+
+#[make_builder((name?, retries?, timeout: Option<f64>), schema)]
+pub struct AppConfig {
+    /// The name shown in logs.
+    #[value("app".to_owned())]
+    pub name: String,
+    #[value(-1)]
+    pub retries: i32,
+    pub database: DatabaseConfig,
+    pub features: Vec<String>,
+    #[value(std::time::Duration::from_secs_f64(timeout.unwrap_or(1.0)))]
+    pub timeout: std::time::Duration,
+}
+
+#[test]
+pub fn schema_demo() {
+    use scones::JsonSchema;
+    use serde_json::json;
+
+    assert_eq!(
+        DatabaseConfig::json_schema(),
+        json!({
+            "title": "DatabaseConfig",
+            "type": "object",
+            "properties": {
+                "host": { "type": "string", "description": "The address of the database server." },
+                "port": { "type": "integer", "minimum": 0, "default": 5432 },
+            },
+            "required": ["host"],
+            "additionalProperties": false,
+        })
+    );
+    let schema = AppConfig::json_schema();
+    assert_eq!(schema["required"], json!(["database", "features"]));
+    let properties = &schema["properties"];
+    assert_eq!(
+        properties["name"],
+        json!({ "type": "string", "description": "The name shown in logs.", "default": "app" })
+    );
+    assert_eq!(properties["retries"]["default"], json!(-1));
+    assert_eq!(properties["database"]["title"], json!("DatabaseConfig"));
+    assert_eq!(
+        properties["features"],
+        json!({ "type": "array", "items": { "type": "string" }, "uniqueItems": false })
+    );
+    assert_eq!(properties["timeout"], json!({ "type": "number" }));
+}
+
+/// An example showing a factory which fills a struct with test data.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder]
+/// #[make_factory]
+/// pub struct Customer {
+///     #[factory(n as u64)]
+///     pub id: u64,
+///     #[factory(seq = "user{n}")]
+///     pub username: String,
+///     #[factory(seq = "user{n:03}@example.com")]
+///     pub email: String,
+///     #[factory(18)]
+///     pub age: u32,
+///     pub tags: Vec<String>,
+/// }
+/// ```
+#[make_builder]
+#[make_factory]
+pub struct Customer {
+    #[factory(n as u64)]
+    pub id: u64,
+    #[factory(seq = "user{n}")]
+    pub username: String,
+    #[factory(seq = "user{n:03}@example.com")]
+    pub email: String,
+    #[factory(18)]
+    pub age: u32,
+    pub tags: Vec<String>,
+}
+
+/// An example showing a factory whose builder is fallible, so that the factory's values are
+/// checked as well.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((plan?) -> Result<Self, SubscriptionCheckError>)]
+/// #[make_factory]
+/// pub struct Subscription {
+///     #[factory(seq = "sub{n}")]
+///     pub code: String,
+///     #[value("free".to_owned())]
+///     pub plan: String,
+///     #[check(range = 1..=100)]
+///     #[factory(n as u32 * 10)]
+///     pub seats: u32,
+/// }
+/// ```
+#[make_builder((plan?) -> Result<Self, SubscriptionCheckError>)]
+#[make_factory]
+pub struct Subscription {
+    #[factory(seq = "sub{n}")]
+    pub code: String,
+    #[value("free".to_owned())]
+    pub plan: String,
+    #[check(range = 1..=100)]
+    #[factory(n as u32 * 10)]
+    pub seats: u32,
+}
+
+#[test]
+pub fn factory_demo() {
+    let factory = CustomerFactory::new();
+    let first = factory.build();
+    assert_eq!((first.id, &first.username[..]), (1, "user1"));
+    assert_eq!((&first.email[..], first.age), ("user001@example.com", 18));
+    assert!(first.tags.is_empty());
+
+    let adults = CustomerFactory::new();
+    let customers =
+        adults.build_many_with(3, |builder| builder.age(40).tags(vec!["vip".to_owned()]));
+    let usernames: Vec<_> = customers.iter().map(|c| &c.username[..]).collect();
+    assert_eq!(usernames, ["user1", "user2", "user3"]);
+    assert!(customers.iter().all(|c| c.age == 40 && c.tags == ["vip"]));
+    assert_eq!(factory.build().username, "user2");
+
+    let named = factory.build_with(|builder| builder.username("alice".to_owned()));
+    assert_eq!((named.id, &named.username[..]), (3, "alice"));
+    assert_eq!(factory.build().id, 4);
+
+    let factory = SubscriptionFactory::default();
+    let first = factory.build().unwrap();
+    assert_eq!(
+        (&first.code[..], &first.plan[..], first.seats),
+        ("sub1", "free", 10)
+    );
+    let pro = factory.build_with(|builder| builder.plan("pro".to_owned()));
+    assert_eq!(pro.unwrap().plan, "pro");
+    let rest = factory.build_many(9);
+    assert_eq!(rest[7].as_ref().unwrap().seats, 100);
+    assert_eq!(
+        rest[8].as_ref().err(),
+        Some(&SubscriptionCheckError::SeatsRange)
+    );
+}
+
+/// An example showing a struct whose arbitrary values are created through its constructor.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_constructor(pub new -> Result<Self, DiscountCheckError>, arbitrary)]
+/// pub struct Discount {
+///     #[check(range = 1..101)]
+///     pub percent: u8,
+///     pub code: String,
+///     #[value(percent >= 50)]
+///     pub large: bool,
+/// }
+/// ```
+#[make_constructor(pub new -> Result<Self, DiscountCheckError>, arbitrary)]
+#[derive(Debug)]
+pub struct Discount {
+    #[check(range = 1..101)]
+    pub percent: u8,
+    pub code: String,
+    #[value(percent >= 50)]
+    pub large: bool,
+}
+
+#[test]
+pub fn arbitrary_demo() {
+    use arbitrary::{Arbitrary, Error, Unstructured};
+
+    let data: Vec<u8> = (0..=255).cycle().step_by(7).take(4096).collect();
+    let mut unstructured = Unstructured::new(&data);
+    let (mut generated, mut discarded) = (0, 0);
+    while !unstructured.is_empty() {
+        match Discount::arbitrary(&mut unstructured) {
+            Ok(discount) => {
+                assert!((1..=100).contains(&discount.percent));
+                assert_eq!(discount.large, discount.percent >= 50);
+                generated += 1;
+            }
+            Err(Error::IncorrectFormat) => discarded += 1,
+            Err(err) => panic!("unexpected error: {}", err),
+        }
+    }
+    assert!(generated > 0 && discarded > 0);
+
+    let mut unstructured = Unstructured::new(&[0, 0, 0, 0]);
+    let result = Discount::arbitrary(&mut unstructured);
+    assert!(matches!(result, Err(Error::IncorrectFormat)));
+}
+
+/// An example showing every combination of several values being built for a test.
+///
+/// It is defined as follows:
+/// ```
+/// # use scones::*;
+/// #[make_builder((port?, tls?) -> Result<Self, SocketCheckError>, matrix)]
+/// pub struct Socket<T> {
+///     #[check(range = 1..)]
+///     #[value(80)]
+///     pub port: u16,
+///     #[value(false)]
+///     pub tls: bool,
+///     pub handler: T,
+/// }
+/// ```
+#[make_builder((port?, tls?) -> Result<Self, SocketCheckError>, matrix)]
+#[derive(Debug)]
+pub struct Socket<T> {
+    #[check(range = 1..)]
+    #[value(80)]
+    pub port: u16,
+    #[value(false)]
+    pub tls: bool,
+    pub handler: T,
+}
+
+#[test]
+pub fn matrix_demo() {
+    use scones::BuildError;
+
+    let sockets = SocketMatrix::new()
+        .handler("echo")
+        .port_each([80, 443, 0])
+        .tls_each([true, false])
+        .build_all()
+        .unwrap();
+    let results: Vec<_> = sockets
+        .map(|result| result.map(|socket| (socket.port, socket.tls)))
+        .collect();
+    assert_eq!(
+        results,
+        [
+            Ok((80, true)),
+            Ok((80, false)),
+            Ok((443, true)),
+            Ok((443, false)),
+            Err(SocketCheckError::PortRange),
+            Err(SocketCheckError::PortRange),
+        ]
+    );
+
+    let mut sockets = SocketMatrix::new().handler(1).build_all().unwrap();
+    let socket = sockets.next().unwrap().unwrap();
+    assert_eq!((socket.port, socket.tls, socket.handler), (80, false, 1));
+    assert!(sockets.next().is_none());
+
+    let empty = SocketMatrix::new().handler(1).port_each([]).build_all();
+    assert_eq!(empty.unwrap().count(), 0);
+    let error = SocketMatrix::<i32>::new().tls(true).build_all().err();
+    assert_eq!(error, Some(BuildError::Missing(vec!["handler"])));
+}
@@ -0,0 +1,163 @@
+//! Command-line parsing for builders made with the `cli` option.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// The error returned by `from_args()` functions generated by the `cli` option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CliError {
+    /// `--help` or `-h` was given. Contains the help text, which should be shown to the user.
+    Help(String),
+    /// An argument was given which is not one of the flags.
+    Unknown(String),
+    /// A flag which needs a value was not followed by one.
+    MissingValue(String),
+    /// The value of a flag could not be parsed.
+    Invalid { flag: String, message: String },
+    /// The flags of required parameters which were not given.
+    Missing(Vec<String>),
+    /// Every flag was valid, but the builder returned an error. Contains the error's message.
+    Failed(String),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Help(help) => f.write_str(help),
+            Self::Unknown(arg) => write!(f, "unexpected argument `{}`", arg),
+            Self::MissingValue(flag) => write!(f, "`{}` needs a value", flag),
+            Self::Invalid { flag, message } => {
+                write!(f, "invalid value for `{}`: {}", flag, message)
+            }
+            Self::Missing(flags) => write!(f, "missing required flags `{}`", flags.join("`, `")),
+            Self::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+#[doc(hidden)]
+/// Describes a parameter of a builder for the command-line parser.
+pub struct CliParam__ {
+    pub name: &'static str,
+    pub ty: &'static str,
+    pub required: bool,
+    /// Whether the parameter is a `bool` which is set to true by giving its flag without a value,
+    /// or to false by giving its flag with `no-` in front of its name.
+    pub switch: bool,
+    pub default: Option<&'static str>,
+    pub help: &'static str,
+}
+
+fn flag_for(name: &str) -> String {
+    format!("--{}", name.replace('_', "-"))
+}
+
+#[doc(hidden)]
+/// Used to implement the `cli_help()` function of structs.
+pub fn cli_help__(params: &[CliParam__]) -> String {
+    let mut lines = Vec::new();
+    for param in params {
+        let usage = if param.switch {
+            format!("--[no-]{}", param.name.replace('_', "-"))
+        } else {
+            format!("{} <{}>", flag_for(param.name), param.ty)
+        };
+        let mut help = param.help.to_owned();
+        if param.required {
+            help.push_str(" [required]");
+        }
+        if let Some(default) = param.default {
+            help.push_str(&format!(" [default: {}]", default));
+        }
+        lines.push((usage, help.trim().to_owned()));
+    }
+    lines.push(("-h, --help".to_owned(), "Print help".to_owned()));
+    let width = lines
+        .iter()
+        .map(|(usage, _)| usage.len())
+        .max()
+        .unwrap_or(0);
+    let mut result = "Options:\n".to_owned();
+    for (usage, help) in lines {
+        result.push_str(&format!("  {:width$}  {}\n", usage, help, width = width));
+    }
+    result
+}
+
+#[doc(hidden)]
+/// Used to implement the `from_args()` function of structs. Holds the text given for each
+/// parameter, where later flags replace earlier ones.
+pub struct ArgParser__ {
+    params: &'static [CliParam__],
+    values: Vec<Option<String>>,
+}
+
+impl ArgParser__ {
+    pub fn new<I>(params: &'static [CliParam__], args: I) -> Result<Self, CliError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut values = vec![None; params.len()];
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            if arg == "-h" || arg == "--help" {
+                return Err(CliError::Help(cli_help__(params)));
+            }
+            let (flag, inline_value) = match arg.find('=') {
+                Some(index) => (&arg[..index], Some(arg[index + 1..].to_owned())),
+                None => (&arg[..], None),
+            };
+            let index = match params.iter().position(|param| flag_for(param.name) == flag) {
+                Some(index) => index,
+                None => {
+                    let negated = params.iter().position(|param| {
+                        param.switch
+                            && flag.strip_prefix("--no-") == Some(&flag_for(param.name)[2..])
+                    });
+                    match (negated, inline_value) {
+                        (Some(index), None) => {
+                            values[index] = Some("false".to_owned());
+                            continue;
+                        }
+                        _ => return Err(CliError::Unknown(arg.clone())),
+                    }
+                }
+            };
+            let value = match inline_value {
+                Some(value) => value,
+                None if params[index].switch => "true".to_owned(),
+                None => args
+                    .next()
+                    .ok_or_else(|| CliError::MissingValue(flag.to_owned()))?,
+            };
+            values[index] = Some(value);
+        }
+        Ok(Self { params, values })
+    }
+
+    /// Parses the value given for the parameter at `index`, if there is one.
+    pub fn take<T: FromStr>(&mut self, index: usize) -> Result<Option<T>, CliError>
+    where
+        T::Err: Display,
+    {
+        match self.values[index].take() {
+            Some(text) => text
+                .parse()
+                .map(Some)
+                .map_err(|err: T::Err| CliError::Invalid {
+                    flag: flag_for(self.params[index].name),
+                    message: err.to_string(),
+                }),
+            None => Ok(None),
+        }
+    }
+}
+
+#[doc(hidden)]
+/// Converts the names of missing parameters to the flags that set them.
+pub fn missing_flags__(names: Vec<&'static str>) -> CliError {
+    CliError::Missing(names.into_iter().map(flag_for).collect())
+}
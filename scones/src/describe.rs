@@ -0,0 +1,118 @@
+//! Runtime descriptions of the constructors and builders generated for a struct.
+
+/// Implemented by `#[make_describe]` and `#[derive(Describe)]` for structs with constructors or
+/// builders generated by this crate. Gives access to the names and types of their parameters at
+/// runtime, along with the documentation of the struct's fields, so that tools can render forms or
+/// documentation for them.
+/// ```
+/// use scones::{make_builder, make_describe, Describe, ParamKind};
+///
+/// #[make_builder((port?))]
+/// #[make_describe]
+/// struct Config {
+///     /// The name of the service.
+///     name: String,
+///     #[value(8000 + 80)]
+///     port: u16,
+///     #[value(Vec::new())]
+///     tags: Vec<&'static str>,
+/// }
+///
+/// let builder = Config::describe().builder("ConfigBuilder").unwrap();
+/// let port = builder.param("port").unwrap();
+/// assert_eq!((port.type_name, port.kind), ("u16", ParamKind::Override));
+/// assert_eq!(port.default, Some("8000 + 80"));
+/// assert_eq!(Config::describe().field("name").unwrap().doc, "The name of the service.");
+/// assert_eq!(Config::describe().field("tags").unwrap().type_name, "Vec<&'static str>");
+/// ```
+/// Types are written without spaces around `<`, `>`, `::` and `&`. Expressions are given exactly as
+/// the compiler writes out their tokens, which can put spaces between tokens that would usually be
+/// written together, like `vec! [1]`.
+pub trait Describe {
+    /// Describes the constructors, builders and fields of this type.
+    fn describe() -> &'static Description;
+}
+
+/// The constructors, builders and fields of a struct, as returned by `Describe::describe()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Description {
+    /// The name of the struct.
+    pub name: &'static str,
+    /// The fields of the struct in the order they are declared. Fields of tuple structs are named
+    /// `field_0`, `field_1` and so on.
+    pub fields: &'static [FieldDescription],
+    /// The constructors of the struct in the order they are declared.
+    pub constructors: &'static [ItemDescription],
+    /// The builders of the struct in the order they are declared.
+    pub builders: &'static [ItemDescription],
+}
+
+impl Description {
+    /// Returns the field with the given name, if there is one.
+    pub fn field(&self, name: &str) -> Option<&'static FieldDescription> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Returns the constructor with the given name, like `new`, if there is one.
+    pub fn constructor(&self, name: &str) -> Option<&'static ItemDescription> {
+        self.constructors.iter().find(|item| item.name == name)
+    }
+
+    /// Returns the builder with the given name, like `ConfigBuilder`, if there is one.
+    pub fn builder(&self, name: &str) -> Option<&'static ItemDescription> {
+        self.builders.iter().find(|item| item.name == name)
+    }
+}
+
+/// A field of a struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldDescription {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    /// The expression given by the field's `#[value()]` attribute, if it has one which is not
+    /// specific to a single constructor or builder.
+    pub default: Option<&'static str>,
+    /// The field's documentation comments, with one line for each line of the comments.
+    pub doc: &'static str,
+}
+
+/// A constructor or builder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemDescription {
+    pub name: &'static str,
+    /// The parameters of a constructor in the order they are taken, or the setters of a builder.
+    pub params: &'static [ParamDescription],
+}
+
+impl ItemDescription {
+    /// Returns the parameter with the given name, if there is one.
+    pub fn param(&self, name: &str) -> Option<&'static ParamDescription> {
+        self.params.iter().find(|param| param.name == name)
+    }
+}
+
+/// A parameter of a constructor or builder.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParamDescription {
+    pub name: &'static str,
+    /// The type of value the parameter takes. For optional parameters, this is the type given to
+    /// the setter rather than the `Option` around it.
+    pub type_name: &'static str,
+    pub kind: ParamKind,
+    /// The expression used when an override parameter is not set.
+    pub default: Option<&'static str>,
+}
+
+/// How a parameter of a constructor or builder is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamKind {
+    /// A parameter for a field which must be given.
+    Required,
+    /// A custom parameter of a builder with an `Option` type, which does not have to be set.
+    Optional,
+    /// A parameter for a field which already has a value that can be replaced by setting it.
+    Override,
+    /// A parameter which must be given but is not one of the fields, like `(size: f32)` or a
+    /// parameter of a flattened field.
+    Custom,
+}
@@ -0,0 +1,85 @@
+//! Support for the string-keyed builders generated by the `dynamic` option.
+
+use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// The error returned when a value cannot be given to a parameter of a dynamic builder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SetError {
+    /// The builder does not have a parameter with this name.
+    Unknown(String),
+    /// The value given to `set()` was not of the parameter's type.
+    WrongType {
+        param: &'static str,
+        expected: &'static str,
+    },
+    /// The text given to `set_str()` could not be parsed into the parameter's type.
+    Invalid {
+        param: &'static str,
+        message: String,
+    },
+}
+
+impl Display for SetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "there is no parameter named `{}`", name),
+            Self::WrongType { param, expected } => {
+                write!(
+                    f,
+                    "`{}` must be given a value of type `{}`",
+                    param, expected
+                )
+            }
+            Self::Invalid { param, message } => {
+                write!(f, "invalid value for `{}`: {}", param, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SetError {}
+
+#[doc(hidden)]
+/// Used to implement `set_str()`. Method resolution picks `ParseStr__` when `T` implements
+/// `FromStr` and falls back to `CannotParseStr__` otherwise, so that parameters which cannot be
+/// parsed do not stop the rest of the builder from working.
+pub struct StrParser__<T>(PhantomData<T>);
+
+impl<T> StrParser__<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for StrParser__<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ParseStr__<T> {
+    fn parse_str__(&self, text: &str) -> Result<T, String>;
+}
+
+impl<T: FromStr> ParseStr__<T> for StrParser__<T>
+where
+    T::Err: Display,
+{
+    fn parse_str__(&self, text: &str) -> Result<T, String> {
+        text.parse().map_err(|err: T::Err| err.to_string())
+    }
+}
+
+#[doc(hidden)]
+pub trait CannotParseStr__<T> {
+    fn parse_str__(&self, text: &str) -> Result<T, String>;
+}
+
+impl<T> CannotParseStr__<T> for &StrParser__<T> {
+    fn parse_str__(&self, _text: &str) -> Result<T, String> {
+        Err("this parameter cannot be parsed from text".to_owned())
+    }
+}
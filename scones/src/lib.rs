@@ -24,9 +24,33 @@
 //! let identity = MyData::identity();
 //! ```
 //!
+//! The same functionality is also available through `#[derive(Constructor, Builder)]` for code
+//! that prefers derive macros over attribute macros. Getters and setters for fields can be
+//! generated with `#[make_accessors]`.
+//!
 //! Documented examples can be found at [https://docs.rs/scones_examples](https://docs.rs/scones_examples)
 
+#[cfg(feature = "cli")]
+mod cli;
+mod describe;
+mod dynamic;
+#[cfg(feature = "schema")]
+mod schema;
+
+#[cfg(feature = "cli")]
+pub use cli::*;
+pub use describe::*;
+pub use dynamic::*;
+#[cfg(feature = "schema")]
+pub use schema::*;
+
+use std::convert::Infallible;
+use std::env::{self, VarError};
+use std::fmt::{self, Debug, Display, Formatter};
 use std::marker::PhantomData;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ptr;
+use std::str::FromStr;
 
 /// Proc macro to generate builders for structs.
 ///
@@ -48,12 +72,17 @@ use std::marker::PhantomData;
 ///     .string("Hello World".to_owned())
 ///     .build();
 /// ```
+/// Each setter has the same name as the parameter it sets. The only exception is parameters named
+/// `new` or `build` (`call` for builders of functions), which would clash with the builder's own
+/// functions. Their setters get trailing underscores, as many as it takes for the name to differ
+/// from every parameter and every other setter: `new_()` usually, but `new__()` if there is also a
+/// parameter named `new_`.
 ///
 /// # Syntax
 /// The full syntax of this macro is as follows:
 /// ```
 /// # /* This little bit of trickery makes this not be tested without telling doc readers.
-/// #[make_builder(visibility name params return_type)]
+/// #[make_builder(visibility name params return_type, options)]
 /// # */
 /// ```
 /// Each of these elements are optional but must always be present in the order listed above. If an
@@ -108,6 +137,9 @@ use std::marker::PhantomData;
 /// #[make_builder(-> Result<Self, FileError>)]
 /// # */
 /// ```
+/// To collect the errors of every field instead of stopping at the first one, use
+/// `-> Result<Self, scones::Errors<FileError>>`. See the "Accumulating Errors" section of
+/// `#[make_constructor]` for more details.
 ///
 /// # Value Attributes
 /// You can use the `#[value()]` attribute to add custom code for initializing a field:
@@ -214,7 +246,8 @@ use std::marker::PhantomData;
 /// provided, it will *override* the default value of `data`.
 ///
 /// # Templates and Tuple Structs
-/// All the above semantics work with templated structs:
+/// All the above semantics work with templated structs, including ones with lifetimes, const
+/// parameters and parameters that have defaults:
 /// ```
 /// use scones::make_builder;
 ///
@@ -228,6 +261,23 @@ use std::marker::PhantomData;
 ///
 /// let instance = MyStructBuilder::new().data(123).build();
 /// ```
+/// `new()` is generic over every parameter of the struct, so the parameters are fixed as soon as
+/// the builder is created. Adding the `untyped` option lets setters change them instead: when a
+/// required field's type is exactly one of the struct's type parameters and no other parameter of
+/// the builder mentions it, the setter for that field can change the parameter. The builder then
+/// starts out using `()` as a placeholder for it, so `new()` is no longer generic over it:
+/// ```
+/// use scones::{make_builder, Missing};
+///
+/// #[make_builder(untyped)]
+/// struct MyStruct<T: ToString> {
+///     data: T,
+/// };
+///
+/// let builder: MyStructBuilder<(), Missing> = MyStructBuilder::new();
+/// let builder: MyStructBuilder<i32, _> = builder.data(123);
+/// let instance: MyStruct<&str> = builder.data("text").build();
+/// ```
 /// All the above semantics are supported with tuple structs as well, the only difference being that
 /// fields are given the names `field_0`, `field_1`, etc.
 /// ```
@@ -241,9 +291,329 @@ use std::marker::PhantomData;
 ///
 /// let instance = MyTupleBuilder::new().field_0(123).build();
 /// ```
+///
+/// # Functions and Methods
+/// `#[make_builder]` can also be placed on a function, giving it named and optional arguments.
+/// Each argument is treated like a field, and `#[value()]` attributes can be placed on arguments
+/// in the same way. Instead of `build()`, the builder has a `call()` function which calls the
+/// original function and returns its result. A function which starts the builder is also
+/// generated, named after the builder in snake case:
+/// ```
+/// use scones::make_builder;
+///
+/// #[make_builder((port?))]
+/// fn connect(host: &str, #[value(80)] port: u16) -> String {
+///     format!("{}:{}", host, port)
+/// }
+///
+/// assert_eq!(connect_builder().host("localhost").call(), "localhost:80");
+/// assert_eq!(ConnectBuilder::new().port(8080).host("example.com").call(), "example.com:8080");
+/// ```
+/// The default name of the builder is the name of the function in PascalCase followed by
+/// `Builder`, and when no name is given it has the same visibility as the function. Builders for
+/// functions cannot specify a return type.
+///
+/// To make builders for methods, `#[make_builder]` must also be placed on the impl block
+/// containing them, with no arguments. The functions which start the builders are then added to
+/// the impl block. If the method takes `self`, the function starting the builder takes it in the
+/// same way and the builder holds on to it until `call()`:
+/// ```
+/// use scones::make_builder;
+///
+/// struct Counter(u32);
+///
+/// #[make_builder]
+/// impl Counter {
+///     #[make_builder((by?))]
+///     fn increment(&mut self, #[value(1)] by: u32) -> u32 {
+///         self.0 += by;
+///         self.0
+///     }
+/// }
+///
+/// let mut counter = Counter(0);
+/// assert_eq!(counter.increment_builder().call(), 1);
+/// assert_eq!(counter.increment_builder().by(5).call(), 6);
+/// ```
+/// Generic functions, `impl Trait` arguments, `async fn` and `unsafe fn` are all supported. The
+/// `call()` function of a builder for an `async fn` is also `async`, and likewise for `unsafe`.
+///
+/// # Options
+/// A comma-separated list of options can be given after the other arguments. When there are no
+/// other arguments, the options can be given on their own, like `#[make_builder(serde)]`.
+///
+/// ### Serde
+/// When the `serde` feature of this crate is enabled, the `serde` option implements `Deserialize`
+/// for the struct. The input is read as a map with an entry for each parameter of the builder,
+/// which are passed to the builder's setters before calling `build()`. This means `#[value()]`
+/// defaults, override and optional parameters, and checks all apply to deserialized data in the
+/// same way as they do to code using the builder. Required parameters which are missing from the
+/// input cause an error listing their names, as do entries which are not parameters. Errors
+/// returned by a fallible builder are converted with `serde::de::Error::custom`:
+#[cfg_attr(feature = "serde", doc = "```")]
+#[cfg_attr(not(feature = "serde"), doc = "```ignore")]
+/// use scones::make_builder;
+///
+/// #[make_builder((port?, workers: Option<usize>), serde)]
+/// struct Config {
+///     name: String,
+///     #[value(8080)]
+///     port: u16,
+///     #[value(workers.unwrap_or(4) * 2)]
+///     threads: usize,
+/// }
+///
+/// let config: Config = serde_json::from_str(r#"{ "name": "api", "workers": 1 }"#).unwrap();
+/// assert_eq!((&config.name[..], config.port, config.threads), ("api", 8080, 2));
+/// assert!(serde_json::from_str::<Config>(r#"{ "port": 80 }"#).is_err());
+/// assert!(serde_json::from_str::<Config>(r#"{ "name": "api", "threads": 3 }"#).is_err());
+/// ```
+/// Only one builder of each struct can use this option. Since every parameter is read as an
+/// `Option`, a `null` value is treated the same as a missing one.
+///
+/// ### Partial
+/// The `partial` option generates a struct named `<StructName>Partial` with a public `Option`
+/// field for each parameter of the builder, along with the same setters. Partial values can be
+/// combined with `merge()`, where values set in the argument replace those in `self`, which makes
+/// it easy to layer configuration from several sources. Whether the required values were given is
+/// only checked when `build()` is called, which returns a `scones::BuildError` if any are missing
+/// or if the builder itself fails. Since the fields are public, partial values can also be written
+/// as struct literals:
+/// ```
+/// use scones::{make_builder, BuildError};
+///
+/// #[make_builder((port?), partial)]
+/// struct Config {
+///     name: String,
+///     #[value(8080)]
+///     port: u16,
+/// }
+///
+/// let defaults = ConfigPartial::new().name("default".to_owned());
+/// let file = ConfigPartial {
+///     port: Some(80),
+///     ..Default::default()
+/// };
+/// let config = defaults.merge(file).build().unwrap();
+/// assert_eq!((&config.name[..], config.port), ("default", 80));
+/// let error = ConfigPartial::new().port(80).build().err();
+/// assert_eq!(error, Some(BuildError::Missing(vec!["name"])));
+/// ```
+/// Whether a type implements `FromStr` is decided once, where the struct is defined. A parameter
+/// whose type is a type parameter of the struct, like `T`, is treated as never implementing it, so
+/// `set_str()` always returns `SetError::Invalid` for it, even when the builder is used with a
+/// type which could be parsed:
+/// ```
+/// use scones::{make_builder, SetError};
+///
+/// #[make_builder(dynamic)]
+/// struct Wrapper<T> {
+///     value: T,
+/// }
+///
+/// let mut builder = WrapperDynBuilder::<u16>::new();
+/// let result = builder.set_str("value", "80");
+/// assert!(matches!(result, Err(SetError::Invalid { param: "value", .. })));
+/// builder.set("value", Box::new(80u16)).unwrap();
+/// assert_eq!(builder.build().unwrap().value, 80);
+/// ```
+/// Only one builder of each struct can use this option.
+///
+/// ### Cli
+/// When the `cli` feature of this crate is enabled, the `cli` option generates a `from_args()`
+/// function which creates the struct from command-line flags, and a `cli_help()` function which
+/// describes them. Each parameter of the builder becomes a flag named after it in kebab-case,
+/// which can be given as `--flag value` or `--flag=value` and is parsed with `FromStr`. Flags for
+/// `bool` parameters can be given without a value to set them to `true`, or with `no-` in front of
+/// their name, like `--no-flag`, to set them to `false`. The help text for each
+/// flag is taken from the first paragraph of the field's doc comment, along with its `#[value()]`
+/// default if it has one:
+#[cfg_attr(feature = "cli", doc = "```")]
+#[cfg_attr(not(feature = "cli"), doc = "```ignore")]
+/// use scones::make_builder;
+///
+/// #[make_builder((port?, verbose?), cli)]
+/// struct Config {
+///     /// The name of the service.
+///     name: String,
+///     #[value(8080)]
+///     port: u16,
+///     #[value(false)]
+///     verbose: bool,
+/// }
+///
+/// // A program would usually pass `std::env::args().skip(1)`.
+/// let config = Config::from_args(vec!["--name", "api", "--port=80", "--verbose"]).unwrap();
+/// assert_eq!((&config.name[..], config.port, config.verbose), ("api", 80, true));
+/// assert!(Config::cli_help().contains("The name of the service."));
+/// ```
+/// Errors are reported as a `scones::CliError`, which lists every required flag that was missing.
+/// When `-h` or `--help` is given, `CliError::Help` is returned containing the text from
+/// `cli_help()`. Only one builder of each struct can use this option.
+///
+/// ### Dynamic
+/// The `dynamic` option generates a struct named `<StructName>DynBuilder`, whose parameters are
+/// set by name instead of through typed setters. `set()` takes the value as a `Box<dyn Any>`,
+/// which must hold exactly the parameter's type, while `set_str()` parses text with `FromStr`.
+/// Both return a `scones::SetError` if the parameter does not exist or the value cannot be used.
+/// Parameters whose types do not implement `FromStr` can only be given to `set()`. `build()` passes the values to the builder's setters, so defaults and
+/// override parameters work the same way as they do for the builder itself:
+/// ```
+/// use scones::{make_builder, BuildError};
+///
+/// #[make_builder((port?), dynamic)]
+/// struct Config {
+///     name: String,
+///     #[value(8080)]
+///     port: u16,
+/// }
+///
+/// let mut builder = ConfigDynBuilder::new();
+/// builder.set("name", Box::new("api".to_owned())).unwrap();
+/// builder.set_str("port", "80").unwrap();
+/// let config = builder.build().unwrap();
+/// assert_eq!((&config.name[..], config.port), ("api", 80));
+/// assert!(ConfigDynBuilder::new().set_str("host", "localhost").is_err());
+/// let error = ConfigDynBuilder::new().build().err();
+/// assert_eq!(error, Some(BuildError::Missing(vec!["name"])));
+/// ```
+/// Whether a type implements `FromStr` is decided once, where the struct is defined. A parameter
+/// whose type is a type parameter of the struct, like `T`, is treated as never implementing it, so
+/// `set_str()` always returns `SetError::Invalid` for it, even when the builder is used with a
+/// type which could be parsed:
+/// ```
+/// use scones::{make_builder, SetError};
+///
+/// #[make_builder(dynamic)]
+/// struct Wrapper<T> {
+///     value: T,
+/// }
+///
+/// let mut builder = WrapperDynBuilder::<u16>::new();
+/// let result = builder.set_str("value", "80");
+/// assert!(matches!(result, Err(SetError::Invalid { param: "value", .. })));
+/// builder.set("value", Box::new(80u16)).unwrap();
+/// assert_eq!(builder.build().unwrap().value, 80);
+/// ```
+/// Only one builder of each struct can use this option.
+///
+/// ### Schema
+/// When the `schema` feature of this crate is enabled, the `schema` option implements
+/// `scones::JsonSchema` for the struct, whose `json_schema()` function returns a `serde_json::Value`
+/// containing a [JSON Schema](https://json-schema.org/). It describes a map with an entry for each
+/// parameter of the builder, which is the same input read by the `serde` option. Required
+/// parameters are listed as required, the documentation of each field is used as its description,
+/// and `#[value()]` expressions which are literals, like `8080` or `"text".to_owned()`, are given
+/// as defaults:
+#[cfg_attr(feature = "schema", doc = "```")]
+#[cfg_attr(not(feature = "schema"), doc = "```ignore")]
+/// use scones::{make_builder, JsonSchema};
+/// use serde_json::json;
+///
+/// #[make_builder(schema)]
+/// struct DatabaseConfig {
+///     url: String,
+/// }
+///
+/// #[make_builder((port?), schema)]
+/// struct Config {
+///     /// The name of the service.
+///     name: String,
+///     #[value(8080)]
+///     port: u16,
+///     database: DatabaseConfig,
+/// }
+///
+/// let schema = Config::json_schema();
+/// assert_eq!(schema["required"], json!(["name", "database"]));
+/// assert_eq!(schema["properties"]["port"]["default"], json!(8080));
+/// assert_eq!(schema["properties"]["database"]["required"], json!(["url"]));
+/// ```
+/// The type of every parameter must implement `JsonSchema` as well. It is implemented for
+/// primitives, strings, `Option` and the standard collections, as well as any other struct which
+/// uses this option, like `DatabaseConfig` above.
+/// Only one builder of each struct can use this option.
+///
+/// ### Matrix
+/// The `matrix` option generates `<StructName>Matrix`, which builds an instance for every
+/// combination of several values, for use in combinatorial tests. Each parameter of the builder
+/// has a setter which takes a single value, and a setter with an `_each` suffix which takes any
+/// number of them. Parameters which are never set use their default value. `build_all()` returns
+/// an iterator which gives each combination to the builder, with the last parameter changing
+/// fastest, or a `BuildError` if a required value was never set:
+/// ```
+/// use scones::make_builder;
+///
+/// #[make_builder((port?, tls?), matrix)]
+/// struct Server {
+///     name: String,
+///     #[value(8080)]
+///     port: u16,
+///     #[value(false)]
+///     tls: bool,
+/// }
+///
+/// let servers = ServerMatrix::new()
+///     .name("api".to_owned())
+///     .port_each([80, 443])
+///     .tls_each([true, false])
+///     .build_all()
+///     .unwrap();
+/// let combinations: Vec<_> = servers.map(|server| (server.port, server.tls)).collect();
+/// assert_eq!(combinations, [(80, true), (80, false), (443, true), (443, false)]);
+/// assert!(ServerMatrix::new().port(80).build_all().is_err());
+/// ```
+/// The type of every parameter must implement `Clone`. Only one builder of each struct can use
+/// this option.
+///
+/// # Environment Variables
+/// Fields can be given an `#[env("VARIABLE")]` attribute to read their values from environment
+/// variables. Builders where any of these fields are override parameters get an
+/// `env_overrides()` function, which parses each variable that is set with `FromStr` and passes
+/// the result to the setter. Every variable that cannot be parsed is reported in a
+/// `scones::Errors<scones::EnvError>`, labeled with the name of the parameter it was for. Since the
+/// type of a builder tracks which required parameters have been given, `env_overrides()` only reads
+/// variables for override parameters. Reading required parameters from the environment needs the
+/// `partial` option, whose partial values get a `from_env()` function which reads variables for
+/// every parameter, including required ones. The builder gets a `from_env()` function too, which
+/// returns the partial value:
+/// ```
+/// use scones::make_builder;
+///
+/// #[make_builder((port?), partial)]
+/// struct Config {
+///     #[env("DOCS_CONFIG_NAME")]
+///     name: String,
+///     #[env("DOCS_CONFIG_PORT")]
+///     #[value(8080)]
+///     port: u16,
+/// }
+///
+/// std::env::set_var("DOCS_CONFIG_PORT", "80");
+/// let config = ConfigBuilder::new().name("a".to_owned()).env_overrides().unwrap().build();
+/// assert_eq!(config.port, 80);
+/// std::env::set_var("DOCS_CONFIG_NAME", "b");
+/// let config = ConfigPartial::from_env().unwrap().build().unwrap();
+/// assert_eq!((&config.name[..], config.port), ("b", 80));
+/// std::env::set_var("DOCS_CONFIG_PORT", "eighty");
+/// let errors = ConfigBuilder::from_env().err().unwrap();
+/// assert_eq!(errors.iter().next().unwrap().error().variable(), "DOCS_CONFIG_PORT");
+/// ```
+/// A variable which none of the generated items would read is an error, such as one on a required
+/// parameter of a builder without the `partial` option:
+/// ```compile_fail
+/// use scones::make_builder;
+///
+/// #[make_builder]
+/// struct Config {
+///     #[env("DOCS_CONFIG_NAME")]
+///     name: String,
+/// }
+/// ```
 pub use scones_macros::make_builder;
 
 pub use scones_macros::generate_items__;
+
 /// Proc macro to generate constructors for structs.
 ///
 /// # Basic Usage
@@ -272,7 +642,7 @@ pub use scones_macros::generate_items__;
 /// The full syntax of this macro is as follows:
 /// ```
 /// # /* This little bit of trickery makes this not be tested without telling doc readers.
-/// #[make_constructor(visibility name params return_type)]
+/// #[make_constructor(visibility name<generics> params return_type where_clause = delegate => inverse)]
 /// # */
 /// ```
 /// Each of these elements are optional but must always be present in the order listed above. If an
@@ -317,6 +687,26 @@ pub use scones_macros::generate_items__;
 /// # */
 /// ```
 ///
+/// ### Generics and Where Clauses
+/// A constructor can have generic parameters and a where clause of its own, written the same way
+/// as they would be on a regular function. They are added to the generated function, not to the
+/// `impl` block it is placed in:
+/// ```
+/// use scones::make_constructor;
+/// use std::convert::TryInto;
+///
+/// #[make_constructor(pub from_iter<I: IntoIterator<Item = u8>>(items: I))]
+/// #[make_constructor(pub from_pair<A>(a: A, b: A) -> Result<Self, A::Error> where A: TryInto<u8>)]
+/// struct MyStruct {
+///     #[value(items.into_iter().collect() for from_iter)]
+///     #[value(vec![a.try_into()?, b.try_into()?] for from_pair)]
+///     data: Vec<u8>,
+/// }
+///
+/// let instance = MyStruct::from_iter(1..4);
+/// let instance = MyStruct::from_pair(1u64, 2u64).unwrap();
+/// ```
+///
 /// ### Return Type
 /// The return type can either be `-> Self` or `-> Result<Self, [any type]>`. Note that the macro
 /// is expecting the literal text `Self` and/or `Result`, it is not capable of recognizing type
@@ -328,6 +718,76 @@ pub use scones_macros::generate_items__;
 /// # */
 /// ```
 ///
+/// ### Delegate
+/// Writing `=` followed by a call to another constructor makes the generated function forward to
+/// that constructor instead of initializing the fields itself. The other constructor can be
+/// generated or written by hand. Constructors which forward to another one do not take any
+/// parameters by default, and can only be given custom parameters:
+/// ```
+/// use scones::make_constructor;
+///
+/// #[make_constructor(pub with_size)]
+/// #[make_constructor(pub unit_square = with_size(1.0, 1.0))]
+/// #[make_constructor(pub square(size: f32) = with_size(size, size))]
+/// struct Rectangle {
+///     width: f32,
+///     height: f32,
+/// }
+///
+/// let instance = Rectangle::unit_square();
+/// let instance = Rectangle::square(2.0);
+/// ```
+/// When the other constructor returns a `Result`, write `?` after the call to pass its error on.
+/// The error is converted with `From` like it usually is:
+/// ```
+/// use scones::make_constructor;
+///
+/// use std::num::ParseIntError;
+///
+/// #[derive(Debug)]
+/// struct MyError(ParseIntError);
+///
+/// impl From<ParseIntError> for MyError {
+///     fn from(err: ParseIntError) -> Self {
+///         Self(err)
+///     }
+/// }
+///
+/// #[make_constructor(pub parse(a: &str, b: &str) -> Result<Self, MyError>
+///     = from_parts(a.parse()?, b)?)]
+/// struct MyStruct {
+///     a: i32,
+///     b: i32,
+/// }
+///
+/// impl MyStruct {
+///     fn from_parts(a: i32, b: &str) -> Result<Self, ParseIntError> {
+///         Ok(Self { a, b: b.parse()? })
+///     }
+/// }
+///
+/// let instance = MyStruct::parse("1", "2").unwrap();
+/// ```
+///
+/// ### Inverse
+/// Writing `=>` followed by a visibility and a name generates a function which takes the struct
+/// apart again, returning the values of its fields as a tuple in the same order as the parameters
+/// of the constructor. This is only possible when every field is initialized directly from the
+/// parameter with the same name, so the constructor cannot have custom parameters and none of the
+/// fields can have a `#[value()]` for it:
+/// ```
+/// use scones::make_constructor;
+///
+/// #[make_constructor(pub new(height, width) => pub into_parts)]
+/// struct Rectangle {
+///     width: f32,
+///     height: f32,
+/// }
+///
+/// let (height, width) = Rectangle::new(2.0, 3.0).into_parts();
+/// assert_eq!((height, width), (2.0, 3.0));
+/// ```
+///
 /// # Value Attributes
 /// You can use the `#[value()]` attribute to add custom code for initializing a field:
 /// ```
@@ -417,7 +877,8 @@ pub use scones_macros::generate_items__;
 /// ```
 ///
 /// # Templates and Tuple Structs
-/// All the above semantics work with templated structs:
+/// All the above semantics work with templated structs, including ones with lifetimes, const
+/// parameters and parameters that have defaults:
 /// ```
 /// use scones::make_constructor;
 ///
@@ -457,32 +918,561 @@ pub use scones_macros::generate_items__;
 /// //     }
 /// // }
 /// ```
+///
+/// # Flattening
+/// The `#[flatten()]` attribute replaces the parameter for a field with the parameters of a
+/// constructor of the field's type, so that composed structs can be created without constructing
+/// each layer separately. Since a macro cannot look at other structs, the constructor's name and
+/// parameters have to be written out:
+/// ```
+/// use scones::{make_builder, make_constructor};
+///
+/// #[make_constructor]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[make_constructor]
+/// #[make_builder]
+/// struct Circle {
+///     #[flatten(new(x: f32, y: f32))]
+///     center: Point,
+///     radius: f32,
+/// }
+///
+/// // The macro generates:
+/// // impl Circle {
+/// //     pub fn new(x: f32, y: f32, radius: f32) -> Self {
+/// //         Self {
+/// //             center: <Point>::new(x, y),
+/// //             radius: radius,
+/// //         }
+/// //     }
+/// // }
+/// let circle = Circle::new(1.0, 2.0, 3.0);
+/// // Builders get a setter for each of the parameters instead.
+/// let circle = CircleBuilder::new().x(1.0).y(2.0).radius(3.0).build();
+/// ```
+/// Leaving the parameters out is an error, even when the field's type has a constructor made by
+/// this crate:
+/// ```compile_fail
+/// use scones::make_constructor;
+///
+/// #[make_constructor]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[make_constructor]
+/// struct Circle {
+///     #[flatten(new)]
+///     center: Point,
+///     radius: f32,
+/// }
+/// ```
+/// Flattening only applies where the field would otherwise be a parameter. When the field has a
+/// `#[value()]`, that is used instead. The names of the flattened parameters must not be the same
+/// as those of any other parameters.
+///
+/// # Checks
+/// The `#[check()]` attribute adds constraints to a field, which are checked by every generated
+/// constructor and builder once the value of the field has been decided. It contains a
+/// comma-separated list of any of the following:
+/// - `range = 1..=10` checks that the value is in the given range.
+/// - `non_empty` checks that `value.is_empty()` is false.
+/// - `len <= 64` compares `value.len()` to the given expression with any comparison operator.
+/// - `fn = is_valid` checks that `is_valid(&value)` returns true.
+///
+/// Anything else, like matching a regular expression, can be checked with `fn =` and a function
+/// of your own.
+///
+/// When a struct has checks, an enum named `<StructName>CheckError` is generated next to it. It has
+/// a variant for each check, named after the field and the check, which implements `Display` and
+/// `Error`. Constructors and builders which return a `Result` convert it into their error type with
+/// `Into` when a check fails. All others panic with a message naming the field, reported at the
+/// location they were called from:
+/// ```
+/// use scones::{make_builder, make_constructor};
+///
+/// fn is_valid_host(host: &str) -> bool {
+///     !host.contains(' ')
+/// }
+///
+/// #[make_constructor]
+/// #[make_constructor(pub try_new -> Result<Self, ServerCheckError>)]
+/// #[make_builder((port?))]
+/// struct Server {
+///     #[check(non_empty, len <= 64, fn = is_valid_host)]
+///     host: String,
+///     #[check(range = 1..=65535)]
+///     #[value(80)]
+///     port: u32,
+/// }
+///
+/// let server = Server::new("localhost".to_owned());
+/// let error = Server::try_new("".to_owned()).err();
+/// assert_eq!(error, Some(ServerCheckError::HostNonEmpty));
+/// let result = std::panic::catch_unwind(|| ServerBuilder::new().host("a b".to_owned()).build());
+/// assert!(result.is_err());
+/// ```
+///
+/// The error is defined by `#[make_constructor]` and `#[make_builder]`, so checks can't be used
+/// with `#[derive(Constructor)]` or `#[derive(Builder)]`, which can't tell whether the other is
+/// also used on the same struct:
+/// ```compile_fail
+/// use scones::Constructor;
+///
+/// #[derive(Constructor)]
+/// struct Server {
+///     #[check(range = 1..=65535)]
+///     port: u32,
+/// }
+/// ```
+///
+/// # Accumulating Errors
+/// Normally, a constructor which returns a `Result` stops at the first `#[value()]` expression
+/// whose `?` fails. If the error type is written as `scones::Errors<E>` or `::scones::Errors<E>`
+/// (the macro only looks at how the type is written, so an imported `Errors` is not recognized),
+/// every field is initialized and checked first, and all of the errors are returned together. Each one is converted to `E`
+/// with `?` or `Into` and labeled with the name of the field it came from. This works the same way
+/// for builders:
+/// ```
+/// use scones::{make_builder, make_constructor};
+/// use std::num::ParseIntError;
+///
+/// #[make_constructor(pub parse(port: &str, threads: &str) -> Result<Self, scones::Errors<ParseIntError>>)]
+/// #[make_builder((port: String, threads: String) -> Result<Self, scones::Errors<ParseIntError>>)]
+/// struct Config {
+///     #[value(port.parse()?)]
+///     port: u16,
+///     #[value(threads.parse()?)]
+///     threads: u8,
+/// }
+///
+/// let errors = Config::parse("http", "-1").err().unwrap();
+/// let fields: Vec<_> = errors.iter().map(|error| error.field()).collect();
+/// assert_eq!(fields, ["port", "threads"]);
+/// let builder = ConfigBuilder::new().port("80".to_owned());
+/// let result = builder.threads("four".to_owned()).build();
+/// assert_eq!(result.err().unwrap().len(), 1);
+/// ```
+///
+/// # Options
+/// Options are given after the other arguments, separated by a comma, like
+/// `#[make_constructor(pub new, arbitrary)]`. When there are no other arguments, the option can be
+/// given by itself, like `#[make_constructor(arbitrary)]`.
+///
+/// ### Arbitrary
+/// When the `arbitrary` feature of this crate is enabled, the `arbitrary` option implements
+/// `arbitrary::Arbitrary` for the struct by drawing a value for each parameter of the constructor
+/// and then calling it. This means that fuzzers and property tests only see values which could have
+/// been created by hand, with every `#[value()]` expression and `#[check()]` applied. If the
+/// constructor returns an error, the input is rejected with `arbitrary::Error::IncorrectFormat`:
+#[cfg_attr(feature = "arbitrary", doc = "```")]
+#[cfg_attr(not(feature = "arbitrary"), doc = "```ignore")]
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use scones::make_constructor;
+///
+/// #[make_constructor(pub new -> Result<Self, DiscountCheckError>, arbitrary)]
+/// struct Discount {
+///     #[check(range = 1..=100)]
+///     percent: u8,
+///     #[value(percent >= 50)]
+///     large: bool,
+/// }
+///
+/// let mut unstructured = Unstructured::new(&[42]);
+/// let discount = Discount::arbitrary(&mut unstructured).unwrap();
+/// assert!(discount.percent == 42 && !discount.large);
+/// ```
+/// The type of every parameter must implement `Arbitrary`, and the constructor cannot have generic
+/// parameters of its own. Only one constructor of each struct can use this option.
 pub use scones_macros::make_constructor;
+/// Used by builders made with the `serde` option so that they work without depending on serde
+/// directly.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde as serde__;
+
+#[cfg(feature = "schema")]
+#[doc(hidden)]
+pub use serde_json as serde_json__;
+
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+pub use arbitrary as arbitrary__;
+
+/// Proc macro to generate getters, setters and `with_*` methods for the fields of a struct.
+///
+/// # Basic Usage
+/// ```
+/// use scones::make_accessors;
+///
+/// #[make_accessors]
+/// struct MyStruct {
+///     count: i32,
+///     name: String,
+/// }
+///
+/// let mut instance = MyStruct { count: 1, name: "a".to_owned() };
+/// let count: i32 = instance.count();
+/// let name: &String = instance.name();
+/// instance.name_mut().push('b');
+/// instance.set_count(2);
+/// let instance = instance.with_name("c".to_owned());
+/// ```
+/// Fields whose types are primitives, shared references or tuples of those are returned by value,
+/// and all other fields are returned by reference. Fields of tuple structs are given the names
+/// `field_0`, `field_1`, etc. like they are in builders.
+///
+/// # Syntax
+/// ```
+/// # /* This little bit of trickery makes this not be tested without telling doc readers.
+/// #[make_accessors(visibility (kinds))]
+/// # */
+/// ```
+/// Visibility defaults to `pub`. Use `pub(self)` to make private accessors. Kinds is a list of
+/// the accessors to generate for every field, which defaults to all of them:
+/// - `get` generates `fn field(&self) -> &T`
+/// - `get_mut` generates `fn field_mut(&mut self) -> &mut T`
+/// - `set` generates `fn set_field(&mut self, value: T)`
+/// - `with` generates `fn with_field(self, value: T) -> Self`
+///
+/// The attribute can be used multiple times, for example to give setters a different visibility
+/// than getters:
+/// ```
+/// # use scones::make_accessors;
+/// #[make_accessors((get))]
+/// #[make_accessors(pub(crate) (set, with))]
+/// struct MyStruct {
+///     data: Vec<u8>,
+/// }
+/// ```
+///
+/// # Field Attributes
+/// Individual fields can be configured with an `#[accessors()]` attribute. `#[accessors(skip)]`
+/// generates no accessors for the field. Otherwise, the attribute can contain a visibility which
+/// replaces the visibility of all of the field's accessors and a list of kinds which limits the
+/// accessors generated for it. Placing `copy` at the start makes the getter return the field by
+/// value, which is useful for `Copy` types that are not detected automatically:
+/// ```
+/// # use scones::make_accessors;
+/// #[derive(Clone, Copy)]
+/// struct Point(f32, f32);
+///
+/// #[make_accessors]
+/// struct MyStruct {
+///     #[accessors(copy pub(crate) (get, set))]
+///     position: Point,
+///     #[accessors((get))]
+///     id: u64,
+///     #[accessors(skip)]
+///     cache: Vec<u8>,
+/// }
+/// ```
+pub use scones_macros::make_accessors;
+
+/// Proc macro to generate constructors and trait implementations for newtypes, which are tuple
+/// structs wrapping a single value.
+///
+/// # Basic Usage
+/// ```
+/// use scones::make_newtype;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct ZeroPort;
+///
+/// fn check_port(port: &u16) -> Result<(), ZeroPort> {
+///     if *port == 0 {
+///         Err(ZeroPort)
+///     } else {
+///         Ok(())
+///     }
+/// }
+///
+/// #[make_newtype(validate = check_port, error = ZeroPort, deref, display, from_str)]
+/// struct Port(u16);
+///
+/// let port = Port::try_new(8080).unwrap();
+/// assert_eq!(Port::try_new(0).err(), Some(ZeroPort));
+/// let port = Port::try_from(80).unwrap();
+/// let inner: &u16 = port.as_ref();
+/// assert_eq!(port.into_inner(), 80);
+/// ```
+///
+/// # Syntax
+/// ```
+/// # /* This little bit of trickery makes this not be tested without telling doc readers.
+/// #[make_newtype(validate = path, error = Type, deref, display, from_str)]
+/// # */
+/// ```
+/// All of the arguments are optional, but `validate` and `error` must be given together. The
+/// validate function is called with a reference to the value and must return
+/// `Result<(), impl Into<Type>>`. The generated functions have the same visibility as the struct,
+/// and the constructor is generated in the same way as those made by `#[make_constructor]`. The
+/// following items are always generated:
+/// - `into_inner(self)`, which returns the wrapped value.
+/// - `AsRef<Inner>`.
+/// - With a validate function, `try_new(inner) -> Result<Self, Type>`, `TryFrom<Inner>` and
+///   `unsafe new_unchecked(inner) -> Self`, which skips validation. `TryFrom` is left out when the
+///   wrapped type is one of the struct's type parameters, like `struct Id<T>(T);`, because it
+///   would overlap with the standard library's `TryFrom` implementation for every `Into` type.
+/// - Without a validate function, `new(inner) -> Self` and `From<Inner>`.
+///
+/// The other arguments opt in to more trait implementations, which all forward to the wrapped
+/// value:
+/// - `deref` implements `Deref<Target = Inner>`. `DerefMut` is never implemented, since it would
+///   allow the value to be changed without being validated.
+/// - `display` implements `Display`.
+/// - `from_str` implements `FromStr`. With a validate function, its error is a
+///   `ParseNewtypeError`, which can hold either the error from parsing the inner value or the
+///   error from validating it.
+///
+/// `#[make_newtype]` can be combined with the other attributes of this crate, as long as they do
+/// not generate functions with the same names.
+pub use scones_macros::make_newtype;
+
+/// Proc macro to generate factories which fill structs with test data. Requires the
+/// `test-factories` feature of this crate.
+///
+/// # Basic Usage
+/// `#[make_factory]` generates a struct named `<StructName>Factory`, which creates instances with
+/// the builder generated by `#[make_builder]`. Its `build()` function gives each parameter of the
+/// builder the value from the `#[factory()]` attribute of the field with the same name. Required
+/// parameters without one are given `Default::default()`, and others are left out, so their
+/// `#[value()]` defaults are used. The builder's `#[check()]` attributes apply as well, and
+/// `build()` returns the same type as the builder does. `build_many(count)` creates several
+/// instances at once. Each instance is given the next number of the factory's sequence, starting
+/// from 1, which is available as `n` in `#[factory()]` expressions. Every factory has its own
+/// sequence, so tests which run at the same time do not change each other's values. The attribute
+/// can also be written as `#[factory(seq = "user{n}")]`, which formats the text with the sequence
+/// number and parses it into the field's type with `FromStr`:
+/// ```
+/// use scones::{make_builder, make_factory};
+///
+/// #[make_builder((active?))]
+/// #[make_factory]
+/// struct User {
+///     #[factory(seq = "user{n}")]
+///     name: String,
+///     #[factory(n * 10)]
+///     score: usize,
+///     #[value(true)]
+///     active: bool,
+///     friends: Vec<String>,
+/// }
+///
+/// let factory = UserFactory::new();
+/// let first = factory.build();
+/// assert_eq!((&first.name[..], first.score, first.active), ("user1", 10, true));
+/// let others = factory.build_many(2);
+/// assert_eq!((&others[1].name[..], others[1].friends.len()), ("user3", 0));
+/// ```
+///
+/// # Overrides
+/// `build_with()` gives the builder, with the factory's values already set, to a closure before
+/// building it, so its setters can choose different values for that instance. The instance still
+/// uses the next number of the factory's sequence. `build_many_with(count, ..)` does the same for
+/// several instances:
+/// ```
+/// # use scones::{make_builder, make_factory};
+/// # #[make_builder((active?))]
+/// # #[make_factory]
+/// # struct User {
+/// #     #[factory(seq = "user{n}")]
+/// #     name: String,
+/// #     #[value(true)]
+/// #     active: bool,
+/// # }
+/// let factory = UserFactory::new();
+/// let inactive = factory.build_many_with(2, |builder| builder.active(false));
+/// assert!(inactive.iter().all(|user| !user.active));
+/// let admin = factory.build_with(|builder| builder.name("admin".to_owned()));
+/// assert_eq!(admin.name, "admin");
+/// assert_eq!(factory.build().name, "user4");
+/// ```
+///
+/// # Syntax
+/// ```
+/// # /* This little bit of trickery makes this not be tested without telling doc readers.
+/// #[make_factory(visibility name for BuilderName)]
+/// # */
+/// ```
+/// Like builders, the name defaults to `<StructName>Factory` and the visibility defaults to `pub`.
+/// The factory uses the builder named `<StructName>Builder` unless another one is given after
+/// `for`, so a struct without that builder cannot have a factory:
+/// ```compile_fail
+/// use scones::make_factory;
+///
+/// #[make_factory]
+/// struct User {
+///     #[factory(seq = "user{n}")]
+///     name: String,
+/// }
+/// ```
+#[cfg(feature = "test-factories")]
+pub use scones_macros::make_factory;
+
+/// Derive macro which generates constructors, as an alternative to `#[make_constructor]`.
+///
+/// Derive macros cannot modify the item they are placed on, which makes them easier to combine
+/// with other tools. Constructors are configured with `#[constructor()]` attributes, which take
+/// the same arguments as `#[make_constructor()]`. Without any `#[constructor()]` attributes, a
+/// single default constructor is generated. `#[value()]` attributes work the same way as they do
+/// for `#[make_constructor]`, except that they are left on the fields.
+/// ```
+/// use scones::{Builder, Constructor};
+///
+/// #[derive(Constructor, Builder)]
+/// #[constructor]
+/// #[constructor(pub new_identical(shared: i32))]
+/// #[builder((b?))]
+/// struct MyStruct {
+///     #[value(shared for new_identical)]
+///     a: i32,
+///     #[value(shared for new_identical)]
+///     #[value(0 for MyStructBuilder)]
+///     b: i32,
+/// }
+///
+/// let instance = MyStruct::new(1, 2);
+/// let instance = MyStruct::new_identical(3);
+/// let instance = MyStructBuilder::new().a(4).build();
+/// ```
+/// `/// ^` documentation comments are only supported by the attribute macros, and the two styles
+/// should not be mixed on the same struct.
+pub use scones_macros::Constructor;
+
+/// Derive macro which generates builders, as an alternative to `#[make_builder]`.
+///
+/// Builders are configured with `#[builder()]` attributes, which take the same arguments as
+/// `#[make_builder()]`. See `#[derive(Constructor)]` for more details.
+/// ```
+/// use scones::Builder;
+///
+/// #[derive(Builder)]
+/// #[builder(pub MyBuilder(data?))]
+/// struct MyStruct {
+///     #[value(10)]
+///     data: i32,
+/// }
+///
+/// let instance = MyBuilder::new().build();
+/// ```
+pub use scones_macros::Builder;
+
+/// Derive macro which implements `scones::Describe`, for structs using `#[derive(Constructor)]` or
+/// `#[derive(Builder)]`.
+///
+/// Only the constructors and builders given by `#[constructor()]` and `#[builder()]` attributes
+/// are described, so the default constructor or builder must be given explicitly with an empty
+/// attribute.
+/// ```
+/// use scones::{Builder, Constructor, Describe};
+///
+/// #[derive(Constructor, Builder, Describe)]
+/// #[constructor]
+/// #[builder((data?))]
+/// struct MyStruct {
+///     #[value(10 for MyStructBuilder)]
+///     data: i32,
+/// }
+///
+/// let description = MyStruct::describe();
+/// assert_eq!(description.constructors[0].name, "new");
+/// assert_eq!(description.builders[0].params[0].default, Some("10"));
+/// ```
+pub use scones_macros::Describe;
+
+/// Proc macro which implements `scones::Describe` for a struct, describing every constructor and
+/// builder generated for it by `#[make_constructor]` and `#[make_builder]`. It does not take any
+/// arguments, and can only be used once on each struct.
+/// ```
+/// use scones::{make_builder, make_constructor, make_describe, Describe};
+///
+/// #[make_constructor]
+/// #[make_builder((data?))]
+/// #[make_describe]
+/// struct MyStruct {
+///     #[value(10 for MyStructBuilder)]
+///     data: i32,
+/// }
+///
+/// let description = MyStruct::describe();
+/// assert_eq!(description.constructors[0].name, "new");
+/// assert_eq!(description.builders[0].params[0].default, Some("10"));
+/// ```
+/// Without it, `scones::Describe` is not implemented:
+/// ```compile_fail
+/// use scones::{make_constructor, Describe};
+///
+/// #[make_constructor]
+/// struct MyStruct {
+///     data: i32,
+/// }
+///
+/// let description = MyStruct::describe();
+/// ```
+pub use scones_macros::make_describe;
 
 /// Indicates that a particular required value has been provided in a builder.
 pub struct Present;
 /// Indicates that a particular required value has not been provided yet in a builder.
 pub struct Missing;
+
+mod sealed {
+    /// Keeps other crates from implementing `FieldStatus`, since builders rely on `Present` and
+    /// `Missing` being the only statuses.
+    pub trait Sealed {}
+
+    impl Sealed for super::Present {}
+    impl Sealed for super::Missing {}
+}
+
+/// Implemented by `Present` and `Missing` so that builders can tell at compile time whether a
+/// required value has been provided. It cannot be implemented outside of this crate:
+/// ```compile_fail
+/// struct Maybe;
+///
+/// impl scones::FieldStatus for Maybe {
+///     const PRESENT: bool = true;
+/// }
+/// ```
+pub trait FieldStatus: sealed::Sealed {
+    #[doc(hidden)]
+    const PRESENT: bool;
+}
+
+impl FieldStatus for Present {
+    const PRESENT: bool = true;
+}
+
+impl FieldStatus for Missing {
+    const PRESENT: bool = false;
+}
+
 #[doc(hidden)]
-/// Used to implement builders.
-pub struct BuilderFieldContainer<FieldType, IsPresent> {
-    data: Option<FieldType>,
+/// Used to implement builders. The value is only initialized when `IsPresent` is `Present`, so
+/// the container is exactly as large as the value it holds.
+pub struct BuilderFieldContainer<FieldType, IsPresent: FieldStatus> {
+    data: MaybeUninit<FieldType>,
     marker_: PhantomData<IsPresent>,
 }
 
-impl<FieldType, IsPresent> BuilderFieldContainer<FieldType, IsPresent> {
+impl<FieldType, IsPresent: FieldStatus> BuilderFieldContainer<FieldType, IsPresent> {
     pub fn set(self, value: FieldType) -> BuilderFieldContainer<FieldType, Present> {
-        BuilderFieldContainer {
-            data: Some(value),
-            marker_: PhantomData,
-        }
+        // Dropping self here takes care of any value that was previously set.
+        BuilderFieldContainer::present(value)
     }
 }
 
 impl<FieldType> BuilderFieldContainer<FieldType, Missing> {
     pub fn missing() -> Self {
         Self {
-            data: None,
+            data: MaybeUninit::uninit(),
             marker_: PhantomData,
         }
     }
@@ -491,13 +1481,248 @@ impl<FieldType> BuilderFieldContainer<FieldType, Missing> {
 impl<FieldType> BuilderFieldContainer<FieldType, Present> {
     pub fn present(value: FieldType) -> Self {
         Self {
-            data: Some(value),
+            data: MaybeUninit::new(value),
             marker_: PhantomData,
         }
     }
 
     pub fn into_value(self) -> FieldType {
-        // The only way for IsPresent to be Present is if the user called set() in the past.
-        self.data.unwrap()
+        let this = ManuallyDrop::new(self);
+        // The only way for IsPresent to be Present is if the user called set() in the past, so the
+        // data is initialized. Wrapping self in ManuallyDrop makes sure it is not dropped twice.
+        unsafe { ptr::read(this.data.as_ptr()) }
+    }
+}
+
+impl<FieldType, IsPresent: FieldStatus> Drop for BuilderFieldContainer<FieldType, IsPresent> {
+    fn drop(&mut self) {
+        if IsPresent::PRESENT {
+            // See into_value().
+            unsafe { ptr::drop_in_place(self.data.as_mut_ptr()) }
+        }
+    }
+}
+
+/// The error returned when parsing a newtype made with `#[make_newtype(validate = .., from_str)]`
+/// fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseNewtypeError<ParseError, ValidateError> {
+    /// The wrapped value could not be parsed.
+    Parse(ParseError),
+    /// The wrapped value was parsed but did not pass validation.
+    Invalid(ValidateError),
+}
+
+impl<P: Display, V: Display> Display for ParseNewtypeError<P, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => Display::fmt(err, f),
+            Self::Invalid(err) => Display::fmt(err, f),
+        }
     }
 }
+
+impl<P: Debug + Display, V: Debug + Display> std::error::Error for ParseNewtypeError<P, V> {}
+
+/// The error returned when building an item whose required values are only checked at runtime,
+/// like the partial values generated by `#[make_builder(partial)]`, the builders generated by
+/// `#[make_builder(dynamic)]` or the matrices generated by `#[make_builder(matrix)]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildError<E = Infallible> {
+    /// The names of the required values which were never given.
+    Missing(Vec<&'static str>),
+    /// Every required value was given, but the builder returned an error.
+    Failed(E),
+}
+
+impl<E: Display> Display for BuildError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(names) => write!(f, "missing values for `{}`", names.join("`, `")),
+            Self::Failed(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for BuildError<E> {}
+
+/// An error which occurred while initializing a particular field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldError<E> {
+    field: &'static str,
+    error: E,
+}
+
+impl<E> FieldError<E> {
+    /// The name of the field which could not be initialized. Fields of tuple structs are named
+    /// after their index, like `"0"`.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// The error which occurred.
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the error which occurred, discarding the name of the field.
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+impl<E: Display> Display for FieldError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.error)
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for FieldError<E> {}
+
+/// Every error which occurred while creating an item whose return type is
+/// `Result<Self, scones::Errors<E>>`. See the "Accumulating Errors" section of `#[make_constructor]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Errors<E> {
+    errors: Vec<FieldError<E>>,
+}
+
+impl<E> Errors<E> {
+    /// Creates an empty collection of errors.
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    /// Adds an error which occurred while initializing `field`.
+    pub fn push(&mut self, field: &'static str, error: E) {
+        self.errors.push(FieldError { field, error });
+    }
+
+    /// Returns the value of `result`, or adds its error and returns `None`.
+    pub fn catch<T>(&mut self, field: &'static str, result: Result<T, E>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.push(field, error);
+                None
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Iterates over the errors in the order the fields are declared in.
+    pub fn iter(&self) -> std::slice::Iter<'_, FieldError<E>> {
+        self.errors.iter()
+    }
+}
+
+impl<E> Default for Errors<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> IntoIterator for Errors<E> {
+    type Item = FieldError<E>;
+    type IntoIter = std::vec::IntoIter<FieldError<E>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a, E> IntoIterator for &'a Errors<E> {
+    type Item = &'a FieldError<E>;
+    type IntoIter = std::slice::Iter<'a, FieldError<E>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
+/// Lists each error on its own line.
+impl<E: Display> Display for Errors<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            Display::fmt(error, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for Errors<E> {}
+
+/// An environment variable named by an `#[env()]` attribute had a value which could not be used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvError {
+    variable: &'static str,
+    message: String,
+}
+
+impl EnvError {
+    /// The name of the environment variable.
+    pub fn variable(&self) -> &'static str {
+        self.variable
+    }
+
+    /// Describes what was wrong with the value of the variable.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for EnvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid value for environment variable `{}`: {}",
+            self.variable, self.message
+        )
+    }
+}
+
+impl std::error::Error for EnvError {}
+
+#[cfg(feature = "test-factories")]
+#[doc(hidden)]
+/// Used to implement `#[factory(seq = "..")]`.
+pub fn sequence__<T: FromStr>(text: String) -> T
+where
+    T::Err: Debug,
+{
+    match text.parse() {
+        Ok(value) => value,
+        Err(err) => panic!("Could not parse the sequence value `{}`: {:?}", text, err),
+    }
+}
+
+#[doc(hidden)]
+/// Used to implement `#[env()]`. Returns `None` if the variable is not set.
+pub fn read_env__<T: FromStr>(variable: &'static str) -> Result<Option<T>, EnvError>
+where
+    T::Err: Display,
+{
+    let text = match env::var(variable) {
+        Ok(text) => text,
+        Err(VarError::NotPresent) => return Ok(None),
+        Err(VarError::NotUnicode(_)) => {
+            return Err(EnvError {
+                variable,
+                message: "the value is not valid unicode".to_owned(),
+            })
+        }
+    };
+    text.parse().map(Some).map_err(|err: T::Err| EnvError {
+        variable,
+        message: err.to_string(),
+    })
+}
@@ -0,0 +1,126 @@
+//! JSON schemas for the parameters of builders made with the `schema` option.
+
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Types which can describe the JSON values they are read from with a
+/// [JSON Schema](https://json-schema.org/). Builders with the `schema` option implement this for
+/// their struct, describing the parameters of the builder. It is also implemented for primitives,
+/// strings and the standard collections, and can be implemented manually for other types which
+/// are used as parameters.
+pub trait JsonSchema {
+    /// Returns a schema which describes values of this type.
+    fn json_schema() -> Value;
+}
+
+macro_rules! impl_schema {
+    ($schema:tt for $($ty:ty),*) => {
+        $(impl JsonSchema for $ty {
+            fn json_schema() -> Value {
+                json!($schema)
+            }
+        })*
+    };
+}
+
+impl_schema!({ "type": "boolean" } for bool);
+impl_schema!({ "type": "integer" } for i8, i16, i32, i64, i128, isize);
+impl_schema!({ "type": "integer", "minimum": 0 } for u8, u16, u32, u64, u128, usize);
+impl_schema!({ "type": "number" } for f32, f64);
+impl_schema!({ "type": "string" } for str, String, Path, PathBuf);
+impl_schema!({ "type": "string", "minLength": 1, "maxLength": 1 } for char);
+
+macro_rules! impl_forward_schema {
+    ($($ty:ident),*) => {
+        $(impl<T: JsonSchema + ?Sized> JsonSchema for $ty<T> {
+            fn json_schema() -> Value {
+                T::json_schema()
+            }
+        })*
+    };
+}
+
+impl_forward_schema!(Box, Rc, Arc);
+
+impl<T: JsonSchema + ?Sized> JsonSchema for &T {
+    fn json_schema() -> Value {
+        T::json_schema()
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Option<T> {
+    fn json_schema() -> Value {
+        json!({ "anyOf": [T::json_schema(), { "type": "null" }] })
+    }
+}
+
+macro_rules! impl_array_schema {
+    ($unique:tt for $($ty:ty),*) => {
+        $(impl<T: JsonSchema> JsonSchema for $ty {
+            fn json_schema() -> Value {
+                json!({ "type": "array", "items": T::json_schema(), "uniqueItems": $unique })
+            }
+        })*
+    };
+}
+
+impl_array_schema!(false for [T], Vec<T>, VecDeque<T>);
+impl_array_schema!(true for BTreeSet<T>);
+
+impl<T: JsonSchema, S> JsonSchema for HashSet<T, S> {
+    fn json_schema() -> Value {
+        json!({ "type": "array", "items": T::json_schema(), "uniqueItems": true })
+    }
+}
+
+impl<T: JsonSchema, const N: usize> JsonSchema for [T; N] {
+    fn json_schema() -> Value {
+        json!({ "type": "array", "items": T::json_schema(), "minItems": N, "maxItems": N })
+    }
+}
+
+impl<K, V: JsonSchema> JsonSchema for BTreeMap<K, V> {
+    fn json_schema() -> Value {
+        json!({ "type": "object", "additionalProperties": V::json_schema() })
+    }
+}
+
+impl<K, V: JsonSchema, S> JsonSchema for HashMap<K, V, S> {
+    fn json_schema() -> Value {
+        json!({ "type": "object", "additionalProperties": V::json_schema() })
+    }
+}
+
+#[doc(hidden)]
+/// Used to implement `JsonSchema` for structs. Adds a description and default to the schema of a
+/// parameter.
+pub fn schema_property__(mut schema: Value, description: &str, default: Option<Value>) -> Value {
+    if let Value::Object(map) = &mut schema {
+        if !description.is_empty() {
+            map.insert("description".to_owned(), description.into());
+        }
+        if let Some(default) = default {
+            map.insert("default".to_owned(), default);
+        }
+    }
+    schema
+}
+
+#[doc(hidden)]
+/// Used to implement `JsonSchema` for structs.
+pub fn object_schema__(title: &str, properties: Vec<(&str, Value)>, required: &[&str]) -> Value {
+    let properties: Map<String, Value> = properties
+        .into_iter()
+        .map(|(name, schema)| (name.to_owned(), schema))
+        .collect();
+    json!({
+        "title": title,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
@@ -0,0 +1,222 @@
+//! Getters, setters and `with_*` methods for the fields of a struct.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::{Comma, Paren};
+use syn::{parenthesized, parse_quote, Error, Field, Ident, Index, Type, Visibility};
+
+#[derive(Clone, Copy, PartialEq)]
+enum AccessorKind {
+    /// `fn field(&self) -> &T`
+    Get,
+    /// `fn field_mut(&mut self) -> &mut T`
+    GetMut,
+    /// `fn set_field(&mut self, value: T)`
+    Set,
+    /// `fn with_field(self, value: T) -> Self`
+    With,
+}
+
+const ALL_KINDS: &[AccessorKind] = &[
+    AccessorKind::Get,
+    AccessorKind::GetMut,
+    AccessorKind::Set,
+    AccessorKind::With,
+];
+
+impl Parse for AccessorKind {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let name = Ident::parse_any(input)?;
+        match &name.to_string()[..] {
+            "get" => Ok(Self::Get),
+            "get_mut" => Ok(Self::GetMut),
+            "set" => Ok(Self::Set),
+            "with" => Ok(Self::With),
+            _ => Err(Error::new_spanned(
+                name,
+                "Expected one of get, get_mut, set or with.",
+            )),
+        }
+    }
+}
+
+fn parse_kinds(input: ParseStream) -> syn::parse::Result<Option<Vec<AccessorKind>>> {
+    if input.peek(Paren) {
+        let content;
+        parenthesized!(content in input);
+        let kinds = content.parse_terminated::<_, Comma>(AccessorKind::parse)?;
+        Ok(Some(kinds.into_iter().collect()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// The arguments of `#[make_accessors]`.
+pub(crate) struct AccessorsInfo {
+    vis: Visibility,
+    kinds: Vec<AccessorKind>,
+}
+
+impl Parse for AccessorsInfo {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let mut vis: Visibility = input.parse()?;
+        // Accessors are usually made for private fields, so they default to public visibility.
+        if let Visibility::Inherited = vis {
+            vis = parse_quote! { pub };
+        }
+        let kinds = parse_kinds(input)?.unwrap_or_else(|| ALL_KINDS.to_vec());
+        Ok(Self { vis, kinds })
+    }
+}
+
+/// The arguments of an `#[accessors()]` attribute on a field.
+#[derive(Default)]
+struct FieldAccessors {
+    skip: bool,
+    copy: bool,
+    vis: Option<Visibility>,
+    kinds: Option<Vec<AccessorKind>>,
+}
+
+impl Parse for FieldAccessors {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let mut result = Self::default();
+        if content.peek(Ident) {
+            let fork = content.fork();
+            let word: Ident = fork.parse()?;
+            if word == "skip" {
+                let _: Ident = content.parse()?;
+                result.skip = true;
+                return Ok(result);
+            } else if word == "copy" {
+                let _: Ident = content.parse()?;
+                result.copy = true;
+            }
+        }
+        let vis: Visibility = content.parse()?;
+        if !matches!(vis, Visibility::Inherited) {
+            result.vis = Some(vis);
+        }
+        result.kinds = parse_kinds(&content)?;
+        Ok(result)
+    }
+}
+
+/// Whether a field of this type should be returned by value instead of by reference. Only types
+/// which are known to be `Copy` without looking at any trait implementations are included.
+fn is_known_copy(ty: &Type) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16",
+        "u32", "u64", "u128", "usize",
+    ];
+    match ty {
+        Type::Path(path) => {
+            path.qself.is_none()
+                && PRIMITIVES
+                    .iter()
+                    .any(|primitive| path.path.is_ident(primitive))
+        }
+        Type::Reference(reference) => reference.mutability.is_none(),
+        Type::Ptr(_) | Type::BareFn(_) | Type::Never(_) => true,
+        Type::Tuple(tuple) => tuple.elems.iter().all(is_known_copy),
+        Type::Paren(paren) => is_known_copy(&paren.elem),
+        Type::Group(group) => is_known_copy(&group.elem),
+        _ => false,
+    }
+}
+
+/// Generates the accessor functions described by `infos` for the given fields.
+pub(crate) fn make_accessors_impl(
+    infos: &[AccessorsInfo],
+    fields: &Punctuated<Field, Comma>,
+) -> Result<Vec<TokenStream2>, Error> {
+    let mut accessors = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let mut field_info = FieldAccessors::default();
+        for attr in &field.attrs {
+            if attr.path.is_ident("accessors") {
+                field_info = syn::parse2(attr.tokens.clone())?;
+            }
+        }
+        if field_info.skip {
+            continue;
+        }
+        let (name, member) = match &field.ident {
+            Some(ident) => (ident.clone(), quote! { #ident }),
+            None => {
+                let member = Index::from(index);
+                (format_ident!("field_{}", index), quote! { #member })
+            }
+        };
+        let ty = &field.ty;
+        let by_value = field_info.copy || is_known_copy(ty);
+        let value = Ident::new("value", Span::mixed_site());
+        for info in infos {
+            let vis = field_info.vis.as_ref().unwrap_or(&info.vis);
+            for kind in &info.kinds {
+                if let Some(kinds) = &field_info.kinds {
+                    if !kinds.contains(kind) {
+                        continue;
+                    }
+                }
+                accessors.push(match kind {
+                    AccessorKind::Get if by_value => {
+                        let doc = format!("Returns the value of `{}`.", name);
+                        quote! {
+                            #[doc = #doc]
+                            #vis fn #name(&self) -> #ty {
+                                self.#member
+                            }
+                        }
+                    }
+                    AccessorKind::Get => {
+                        let doc = format!("Returns a reference to `{}`.", name);
+                        quote! {
+                            #[doc = #doc]
+                            #vis fn #name(&self) -> &#ty {
+                                &self.#member
+                            }
+                        }
+                    }
+                    AccessorKind::GetMut => {
+                        let fn_name = format_ident!("{}_mut", name);
+                        let doc = format!("Returns a mutable reference to `{}`.", name);
+                        quote! {
+                            #[doc = #doc]
+                            #vis fn #fn_name(&mut self) -> &mut #ty {
+                                &mut self.#member
+                            }
+                        }
+                    }
+                    AccessorKind::Set => {
+                        let fn_name = format_ident!("set_{}", name);
+                        let doc = format!("Replaces the value of `{}`.", name);
+                        quote! {
+                            #[doc = #doc]
+                            #vis fn #fn_name(&mut self, #value: #ty) {
+                                self.#member = #value;
+                            }
+                        }
+                    }
+                    AccessorKind::With => {
+                        let fn_name = format_ident!("with_{}", name);
+                        let doc = format!("Returns this item with `{}` replaced.", name);
+                        quote! {
+                            #[doc = #doc]
+                            #vis fn #fn_name(mut self, #value: #ty) -> Self {
+                                self.#member = #value;
+                                self
+                            }
+                        }
+                    }
+                });
+            }
+        }
+    }
+    Ok(accessors)
+}
@@ -0,0 +1,97 @@
+//! Constructors and builders which initialize every field before reporting errors, so that all of
+//! the problems with their inputs are returned at once in a `scones::Errors`.
+
+use crate::{check, FieldInfo};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{GenericArgument, Ident, PathArguments, Type};
+
+/// If the error type of a fallible item is written as `scones::Errors<E>` or `::scones::Errors<E>`,
+/// returns `E`.
+pub(crate) fn accumulated_error(error_type: &Type) -> Option<&Type> {
+    let path = match error_type {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segments: Vec<_> = path.segments.iter().collect();
+    let last = match segments[..] {
+        [first, last] if first.ident == "scones" && first.arguments.is_empty() => last,
+        _ => return None,
+    };
+    if last.ident != "Errors" {
+        return None;
+    }
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Generates code which evaluates `initializers` one field at a time, collecting the errors of any
+/// which fail or do not pass their checks. The result of the code is a `Result` which contains the
+/// instance created by `make_self` only if there were no errors.
+pub(crate) fn make_accumulating(
+    struct_name: &Ident,
+    fields: &[FieldInfo],
+    is_tuple: bool,
+    initializers: Vec<TokenStream2>,
+    make_self: TokenStream2,
+    error_type: &Type,
+) -> TokenStream2 {
+    let errors = Ident::new("errors", Span::mixed_site());
+    let value = Ident::new("value", Span::mixed_site());
+    let mut steps = Vec::new();
+    let mut locals = Vec::new();
+    let mut members = Vec::new();
+    for (index, (field, init)) in fields.iter().zip(initializers).enumerate() {
+        let local = Ident::new(&format!("field_{}", index), Span::mixed_site());
+        let ty = field.ty;
+        let name = if is_tuple {
+            index.to_string()
+        } else {
+            field.ident.to_string()
+        };
+        let checks = check::make_field_checks(struct_name, fields, index, &value, |error| {
+            quote! { #errors.push(#name, ::core::convert::Into::into(#error)); }
+        });
+        // The closure gives `?` in the initializer something to return from other than the
+        // function itself.
+        steps.push(quote! {
+            #[allow(clippy::redundant_closure_call)]
+            let #local = #errors.catch(
+                #name,
+                (|| -> ::core::result::Result<#ty, #error_type> {
+                    ::core::result::Result::Ok(#init)
+                })(),
+            );
+            if let ::core::option::Option::Some(#value) = &#local {
+                #(#checks)*
+            }
+        });
+        members.push(if is_tuple {
+            quote! { #local }
+        } else {
+            let ident = &field.ident;
+            quote! { #ident: #local }
+        });
+        locals.push(local);
+    }
+    let instance = if is_tuple {
+        quote! { #make_self ( #(#members),* ) }
+    } else {
+        quote! { #make_self { #(#members),* } }
+    };
+    quote! {
+        let mut #errors = ::scones::Errors::new();
+        #(#steps)*
+        match (#(#locals,)*) {
+            (#(::core::option::Option::Some(#locals),)*) if #errors.is_empty() => {
+                ::core::result::Result::Ok(#instance)
+            }
+            _ => ::core::result::Result::Err(#errors),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! `Arbitrary` implementations which create the struct through one of its constructors, so that
+//! generated values follow the same rules as values created by hand.
+
+use crate::{constructor_params, ConstructorInfo, FieldInfo, ReturnSemantics};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_quote, Error, Generics, Ident, Lifetime};
+
+/// Generates an implementation of `arbitrary::Arbitrary` for the struct which draws a value for
+/// each parameter of the constructor and then calls it. Errors returned by fallible constructors
+/// are reported as `arbitrary::Error::IncorrectFormat`, so that the input is discarded.
+pub(crate) fn make_arbitrary_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    info: &ConstructorInfo,
+    fields: &[FieldInfo],
+) -> Result<TokenStream2, Error> {
+    if !info.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &info.generics.params,
+            "The arbitrary option cannot be used on constructors with their own generic \
+            parameters.",
+        ));
+    }
+    let arbitrary = quote! { ::scones::arbitrary__ };
+    let name = &info.name;
+    let params = constructor_params(info, fields)?;
+    let lifetime = Lifetime::new("'arbitrary__", Span::call_site());
+    let unstructured = Ident::new("unstructured", Span::mixed_site());
+    let mut impl_generics = generics.clone();
+    impl_generics.params.insert(0, parse_quote! { #lifetime });
+    let predicates = &mut impl_generics.make_where_clause().predicates;
+    for (_, ty) in &params {
+        predicates.push(parse_quote! { #ty: #arbitrary::Arbitrary<#lifetime> });
+    }
+    let args = params
+        .iter()
+        .map(|_| quote! { #arbitrary::Unstructured::arbitrary(#unstructured)? });
+    let call = quote! { Self::#name(#(#args),*) };
+    let body = match info.return_semantics {
+        ReturnSemantics::Selff => quote! { ::core::result::Result::Ok(#call) },
+        ReturnSemantics::Result | ReturnSemantics::Accumulate(_) => quote! {
+            #call.map_err(|_| #arbitrary::Error::IncorrectFormat)
+        },
+    };
+    let (_, type_generics, _) = generics.split_for_impl();
+    let (impl_generics, _, impl_where) = impl_generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #arbitrary::Arbitrary<#lifetime> for #struct_name #type_generics
+        #impl_where
+        {
+            fn arbitrary(
+                #unstructured: &mut #arbitrary::Unstructured<#lifetime>,
+            ) -> #arbitrary::Result<Self> {
+                #body
+            }
+        }
+    })
+}
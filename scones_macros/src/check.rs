@@ -0,0 +1,259 @@
+//! Constraints on the values of fields, which are checked whenever an instance is created.
+
+use crate::FieldInfo;
+use inflector::Inflector;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{parenthesized, BinOp, Error, Expr, Ident, Index, Path, Token, Visibility};
+
+#[derive(Clone)]
+pub(crate) enum Check {
+    /// `range = 1..=10`
+    Range(Expr),
+    /// `non_empty`
+    NonEmpty,
+    /// `len <= 64`
+    Len(BinOp, Expr),
+    /// `fn = is_valid`
+    Fn(Path),
+}
+
+impl Parse for Check {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        if input.peek(Token![fn]) {
+            let _: Token![fn] = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            return Ok(Self::Fn(input.parse()?));
+        }
+        let name: Ident = input.parse()?;
+        if name == "range" {
+            let _: Token![=] = input.parse()?;
+            Ok(Self::Range(input.parse()?))
+        } else if name == "non_empty" {
+            Ok(Self::NonEmpty)
+        } else if name == "len" {
+            let op: BinOp = input.parse()?;
+            match op {
+                BinOp::Lt(_)
+                | BinOp::Le(_)
+                | BinOp::Eq(_)
+                | BinOp::Ne(_)
+                | BinOp::Ge(_)
+                | BinOp::Gt(_) => Ok(Self::Len(op, input.parse()?)),
+                _ => Err(Error::new_spanned(op, "Expected a comparison, like `<=`.")),
+            }
+        } else {
+            Err(Error::new_spanned(
+                name,
+                "Expected one of range, non_empty, len or fn.",
+            ))
+        }
+    }
+}
+
+/// Parses the contents of a `#[check()]` attribute.
+pub(crate) fn parse_checks(input: ParseStream) -> syn::parse::Result<Vec<Check>> {
+    let content;
+    parenthesized!(content in input);
+    let checks = Punctuated::<Check, Comma>::parse_terminated(&content)?;
+    Ok(checks.into_iter().collect())
+}
+
+impl Check {
+    /// The part of the error variant's name which describes this check.
+    fn name(&self) -> String {
+        match self {
+            Self::Range(_) => "Range".to_owned(),
+            Self::NonEmpty => "NonEmpty".to_owned(),
+            Self::Len(..) => "Len".to_owned(),
+            Self::Fn(path) => {
+                let last = &path.segments.last().unwrap().ident;
+                last.to_string().to_pascal_case()
+            }
+        }
+    }
+
+    fn message(&self, field: &Ident) -> String {
+        let text = |tokens: TokenStream2| tokens.to_string();
+        match self {
+            Self::Range(range) => format!(
+                "{} must be in the range {}.",
+                field,
+                text(quote! { #range })
+            ),
+            Self::NonEmpty => format!("{} must not be empty.", field),
+            Self::Len(op, len) => format!(
+                "The length of {} must be {} {}.",
+                field,
+                text(quote! { #op }),
+                text(quote! { #len })
+            ),
+            Self::Fn(path) => format!("{} was rejected by {}.", field, text(quote! { #path })),
+        }
+    }
+
+    /// Code which is true when `value`, a reference to the field, passes this check.
+    fn condition(&self, value: &Ident) -> TokenStream2 {
+        match self {
+            Self::Range(range) => quote! { (#range).contains(#value) },
+            Self::NonEmpty => quote! { !#value.is_empty() },
+            Self::Len(op, len) => quote! { #value.len() #op #len },
+            Self::Fn(path) => quote! { #path(#value) },
+        }
+    }
+}
+
+/// Every check on the struct's fields, along with the index of the field it checks and the name
+/// of the error variant for when it fails.
+fn all_checks<'a>(fields: &'a [FieldInfo]) -> Vec<(usize, &'a Check, Ident)> {
+    let mut taken = HashSet::new();
+    let mut result = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        for check in &field.checks {
+            let base = format!(
+                "{}{}",
+                field.ident.to_string().to_pascal_case(),
+                check.name()
+            );
+            let mut name = base.clone();
+            let mut number = 1;
+            while !taken.insert(name.clone()) {
+                number += 1;
+                name = format!("{}{}", base, number);
+            }
+            result.push((index, check, format_ident!("{}", name)));
+        }
+    }
+    result
+}
+
+pub(crate) fn has_checks(fields: &[FieldInfo]) -> bool {
+    fields.iter().any(|field| !field.checks.is_empty())
+}
+
+/// The name of the error generated for a struct with checks.
+pub(crate) fn error_name(struct_name: &Ident) -> Ident {
+    format_ident!("{}CheckError", struct_name)
+}
+
+/// Generates the error type with a variant for each check, if there are any checks.
+pub(crate) fn make_check_error(
+    vis: &Visibility,
+    struct_name: &Ident,
+    fields: &[FieldInfo],
+) -> TokenStream2 {
+    if !has_checks(fields) {
+        return quote! {};
+    }
+    let error_name = error_name(struct_name);
+    let mut variants = Vec::new();
+    let mut messages = Vec::new();
+    for (index, check, variant) in all_checks(fields) {
+        let message = check.message(&fields[index].ident);
+        variants.push(quote! {
+            #[doc = #message]
+            #variant
+        });
+        messages.push(quote! { Self::#variant => #message });
+    }
+    let doc = format!(
+        "Describes which of the checks on the fields of `{}` failed.",
+        struct_name
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #vis enum #error_name {
+            #(#variants),*
+        }
+
+        impl ::core::fmt::Display for #error_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(match self {
+                    #(#messages),*
+                })
+            }
+        }
+
+        impl ::std::error::Error for #error_name {}
+    }
+}
+
+/// Code which runs the checks on the field at `index`, where `value` is a reference to the value of
+/// the field. `fail` is given the error of a failed check and returns the code which handles it.
+pub(crate) fn make_field_checks(
+    struct_name: &Ident,
+    fields: &[FieldInfo],
+    index: usize,
+    value: &Ident,
+    fail: impl Fn(TokenStream2) -> TokenStream2,
+) -> Vec<TokenStream2> {
+    let error_name = error_name(struct_name);
+    all_checks(fields)
+        .into_iter()
+        .filter(|(field_index, ..)| *field_index == index)
+        .map(|(_, check, variant)| {
+            let condition = check.condition(value);
+            let fail = fail(quote! { #error_name::#variant });
+            quote! {
+                if !(#condition) {
+                    #fail
+                }
+            }
+        })
+        .collect()
+}
+
+/// Wraps `make_self` so that the checks are run on the instance it creates. When `fallible` is
+/// true, the result of the code is a `Result` and failed checks are converted to its error type.
+/// Otherwise, failed checks panic.
+pub(crate) fn make_checked(
+    struct_name: &Ident,
+    fields: &[FieldInfo],
+    is_tuple: bool,
+    make_self: TokenStream2,
+    fallible: bool,
+) -> TokenStream2 {
+    let result = Ident::new("result", Span::mixed_site());
+    let value = Ident::new("value", Span::mixed_site());
+    let mut checks = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        if field.checks.is_empty() {
+            continue;
+        }
+        let member = if is_tuple {
+            let index = Index::from(index);
+            quote! { #index }
+        } else {
+            let ident = &field.ident;
+            quote! { #ident }
+        };
+        let field_checks = make_field_checks(struct_name, fields, index, &value, |error| {
+            if fallible {
+                quote! {
+                    return ::core::result::Result::Err(::core::convert::Into::into(#error));
+                }
+            } else {
+                quote! { ::std::panic!("{}", #error); }
+            }
+        });
+        checks.push(quote! {
+            let #value = &#result.#member;
+            #(#field_checks)*
+        });
+    }
+    let finish = if fallible {
+        quote! { ::core::result::Result::Ok(#result) }
+    } else {
+        quote! { #result }
+    };
+    quote! {
+        let #result = #make_self;
+        #(#checks)*
+        #finish
+    }
+}
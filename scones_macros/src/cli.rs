@@ -0,0 +1,108 @@
+//! Parsing command-line arguments into the parameters of a builder.
+
+use crate::partial::make_runtime_build;
+use crate::{BuilderField, RuntimeBuilder};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use std::collections::HashMap;
+use syn::Ident;
+
+/// Generates `from_args()` and `cli_help()` functions on the struct, which use the builder to
+/// create it from command-line flags.
+pub(crate) fn make_cli_impl(builder: &RuntimeBuilder) -> TokenStream2 {
+    let RuntimeBuilder {
+        vis,
+        struct_name,
+        generics,
+        ..
+    } = builder;
+    let builder_name = builder.builder_name.to_string();
+    let parser = Ident::new("parser", Span::mixed_site());
+    let result = Ident::new("result", Span::mixed_site());
+    let mut param_infos = Vec::new();
+    let mut reads = Vec::new();
+    let mut locals = HashMap::new();
+    let mut bounds = Vec::new();
+    for (index, param) in builder.params.iter().enumerate() {
+        let name = param.borrow_name();
+        let ty = param.borrow_type();
+        let field = builder.fields.iter().find(|field| &field.ident == name);
+        let name_str = name.to_string();
+        let ty_str = quote! { #ty }.to_string().replace(' ', "");
+        let required = matches!(param, BuilderField::Required { .. });
+        let switch = ty_str == "bool";
+        let default = match (param, field) {
+            (BuilderField::Override { .. }, Some(field)) => {
+                let init = field
+                    .custom_init
+                    .get(&builder_name)
+                    .or(field.default_init.as_ref());
+                match init {
+                    Some(init) => {
+                        let init = init.to_string();
+                        quote! { ::core::option::Option::Some(#init) }
+                    }
+                    None => quote! { ::core::option::Option::None },
+                }
+            }
+            _ => quote! { ::core::option::Option::None },
+        };
+        let help = field.map(|field| field.summary()).unwrap_or_default();
+        param_infos.push(quote! {
+            ::scones::CliParam__ {
+                name: #name_str,
+                ty: #ty_str,
+                required: #required,
+                switch: #switch,
+                default: #default,
+                help: #help,
+            }
+        });
+        let local = Ident::new(&format!("param_{}", index), Span::mixed_site());
+        reads.push(quote! { let #local = #parser.take::<#ty>(#index)?; });
+        locals.insert(name_str, local);
+        bounds.push(quote! { #ty: ::core::str::FromStr });
+        bounds.push(quote! { <#ty as ::core::str::FromStr>::Err: ::core::fmt::Display });
+    }
+    let build = make_runtime_build(builder, |name| {
+        let local = &locals[&name.to_string()];
+        quote! { #local }
+    });
+    let finish = if builder.error_type.is_some() {
+        quote! {
+            #result.map_err(|err| ::scones::CliError::Failed(::std::string::ToString::to_string(&err)))
+        }
+    } else {
+        quote! { ::core::result::Result::Ok(#result) }
+    };
+    let params = quote! {
+        const PARAMS: &[::scones::CliParam__] = &[#(#param_infos),*];
+    };
+    let (impl_generics, type_generics, generic_where) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics #struct_name #type_generics #generic_where {
+            /// Creates an instance from command-line flags, which are named after the parameters
+            /// of the builder in kebab-case. Use `from_args(std::env::args().skip(1))` to read the
+            /// arguments the program was started with. If `--help` is given, the text returned by
+            /// `cli_help()` is returned as an error.
+            #vis fn from_args<I>(args: I) -> ::core::result::Result<Self, ::scones::CliError>
+            where
+                I: ::core::iter::IntoIterator,
+                I::Item: ::core::convert::Into<::std::string::String>,
+                #(#bounds,)*
+            {
+                #params
+                let mut #parser = ::scones::ArgParser__::new(PARAMS, args)?;
+                #(#reads)*
+                let #result = #build.map_err(::scones::missing_flags__)?;
+                #finish
+            }
+
+            /// Describes the command-line flags accepted by `from_args()`.
+            #vis fn cli_help() -> ::std::string::String {
+                #params
+                ::scones::cli_help__(PARAMS)
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Implementations of `scones::Describe`, which make the constructors and builders of a struct
+//! available at runtime.
+
+use crate::{
+    constructor_params, make_builder_fields, BuilderField, BuilderInfo, ConstructorInfo, FieldInfo,
+};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use std::collections::HashSet;
+use syn::{Error, Generics, Ident, Type};
+
+fn make_option(value: Option<String>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { ::core::option::Option::Some(#value) },
+        None => quote! { ::core::option::Option::None },
+    }
+}
+
+/// Writes out a type without the spaces which are put between all of its tokens, around `<`, `>`,
+/// `::` and `&`, so that `Vec<u8>` is not written as `Vec < u8 >`.
+fn type_name(ty: &Type) -> String {
+    let text = ty.to_token_stream().to_string();
+    let mut result = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ' ' {
+            let joins_previous = matches!(result.chars().last(), Some('<') | Some(':') | Some('&'));
+            // A `>` after a space is never part of `->`, since that is written without one.
+            let joins_next = matches!(
+                chars.peek(),
+                Some('<') | Some('>') | Some(':') | Some(',') | Some(';')
+            );
+            if joins_previous || joins_next {
+                continue;
+            }
+        }
+        result.push(c);
+    }
+    result
+}
+
+fn make_param(name: &Ident, ty: &Type, kind: &str, default: Option<String>) -> TokenStream2 {
+    let name = name.to_string();
+    let type_name = type_name(ty);
+    let kind = Ident::new(kind, Span::call_site());
+    let default = make_option(default);
+    quote! {
+        ::scones::ParamDescription {
+            name: #name,
+            type_name: #type_name,
+            kind: ::scones::ParamKind::#kind,
+            default: #default,
+        }
+    }
+}
+
+fn make_item(name: String, params: Vec<TokenStream2>) -> TokenStream2 {
+    quote! {
+        ::scones::ItemDescription {
+            name: #name,
+            params: &[#(#params),*],
+        }
+    }
+}
+
+fn describe_constructor(
+    info: &ConstructorInfo,
+    fields: &[FieldInfo],
+) -> Result<TokenStream2, Error> {
+    let name = info.name.to_string();
+    let params = constructor_params(info, fields)?
+        .iter()
+        .map(|(param, ty)| {
+            let is_field = fields
+                .iter()
+                .any(|field| &field.ident == param && field.flatten.is_none());
+            let kind = if is_field && info.delegate.is_none() {
+                "Required"
+            } else {
+                "Custom"
+            };
+            make_param(param, ty, kind, None)
+        })
+        .collect();
+    Ok(make_item(name, params))
+}
+
+fn describe_builder(info: &BuilderInfo, fields: &[FieldInfo]) -> Result<TokenStream2, Error> {
+    let name = info.name.to_string();
+    let (_, params) = make_builder_fields(&name, info.params.clone(), fields, &mut HashSet::new())?;
+    let params = params
+        .iter()
+        .map(|param| {
+            let field = fields
+                .iter()
+                .find(|field| &field.ident == param.borrow_name() && field.flatten.is_none());
+            let ty = param.borrow_type();
+            match (param, field) {
+                (BuilderField::Required { name, .. }, Some(_)) => {
+                    make_param(name, ty, "Required", None)
+                }
+                (BuilderField::Required { name, .. }, None) => make_param(name, ty, "Custom", None),
+                (BuilderField::Optional { name, .. }, _) => make_param(name, ty, "Optional", None),
+                (BuilderField::Override { name, .. }, _) => {
+                    let default = field.and_then(|field| {
+                        field
+                            .custom_init
+                            .get(&info.name.to_string())
+                            .or(field.default_init.as_ref())
+                            .map(ToString::to_string)
+                    });
+                    make_param(name, ty, "Override", default)
+                }
+            }
+        })
+        .collect();
+    Ok(make_item(name, params))
+}
+
+/// Generates an implementation of `scones::Describe` for the struct, which lists the given
+/// constructors and builders along with every field.
+pub(crate) fn make_describe_impl(
+    struct_name: &Ident,
+    generics: &Generics,
+    fields: &[FieldInfo],
+    constructors: &[&ConstructorInfo],
+    builders: &[&BuilderInfo],
+) -> Result<TokenStream2, Error> {
+    let name = struct_name.to_string();
+    let field_descriptions = fields.iter().map(|field| {
+        let name = field.ident.to_string();
+        let type_name = type_name(field.ty);
+        let default = make_option(field.default_init.as_ref().map(ToString::to_string));
+        let doc = field.doc.join("\n");
+        quote! {
+            ::scones::FieldDescription {
+                name: #name,
+                type_name: #type_name,
+                default: #default,
+                doc: #doc,
+            }
+        }
+    });
+    let constructors = constructors
+        .iter()
+        .map(|info| describe_constructor(info, fields))
+        .collect::<Result<Vec<_>, _>>()?;
+    let builders = builders
+        .iter()
+        .map(|info| describe_builder(info, fields))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (impl_generics, type_generics, generic_where) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::scones::Describe for #struct_name #type_generics #generic_where {
+            fn describe() -> &'static ::scones::Description {
+                const DESCRIPTION: ::scones::Description = ::scones::Description {
+                    name: #name,
+                    fields: &[#(#field_descriptions),*],
+                    constructors: &[#(#constructors),*],
+                    builders: &[#(#builders),*],
+                };
+                &DESCRIPTION
+            }
+        }
+    })
+}
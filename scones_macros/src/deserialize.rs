@@ -0,0 +1,82 @@
+//! `Deserialize` implementations which create the struct by passing the deserialized values through
+//! one of its builders.
+
+use crate::partial::make_runtime_build;
+use crate::{make_generic_args, make_generics_marker, unique_ident, RuntimeBuilder};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_quote, Ident, Lifetime};
+
+/// Generates a `Deserialize` implementation for the struct which reads a map with an entry for each
+/// parameter of the builder, gives them to the builder's setters and then builds the struct.
+/// Required parameters which are missing from the input and entries which are not parameters are
+/// reported as errors.
+pub(crate) fn make_deserialize_impl(builder: &RuntimeBuilder) -> TokenStream2 {
+    let RuntimeBuilder {
+        struct_name,
+        generics,
+        builder_name,
+        ..
+    } = builder;
+    let serde = quote! { ::scones::serde__ };
+    let params_name = format_ident!("{}Params__", builder_name);
+    let params = Ident::new("params", Span::mixed_site());
+    let result = Ident::new("result", Span::mixed_site());
+    let de = Lifetime::new("'de__", Span::call_site());
+    let mut taken_fields = builder
+        .params
+        .iter()
+        .map(|field| field.borrow_name().to_string())
+        .collect();
+    let marker_field = unique_ident("generics_marker__", &mut taken_fields);
+    let param_defs = builder.params.iter().map(|field| {
+        let name = field.borrow_name();
+        let ty = field.borrow_type();
+        quote! { #name: ::core::option::Option<#ty> }
+    });
+    let build = make_runtime_build(builder, |name| quote! { #params.#name });
+    let finish = if builder.error_type.is_some() {
+        quote! { #result.map_err(<D__::Error as #serde::de::Error>::custom) }
+    } else {
+        quote! { ::core::result::Result::Ok(#result) }
+    };
+
+    let marker = make_generics_marker(generics);
+    let generic_args = make_generic_args(generics);
+    let params_ty = quote! { #params_name <#(#generic_args),*> };
+    let (_, type_generics, generic_where) = generics.split_for_impl();
+    let mut impl_generics = (*generics).clone();
+    impl_generics.params.insert(0, parse_quote! { #de });
+    impl_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote! { #params_ty: #serde::Deserialize<#de> });
+    let (impl_generics, _, impl_where) = impl_generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            #[derive(#serde::Deserialize)]
+            #[serde(crate = "::scones::serde__", deny_unknown_fields)]
+            struct #params_name #generics #generic_where {
+                #(#param_defs,)*
+                #[serde(skip)]
+                #marker_field: #marker,
+            }
+
+            impl #impl_generics #serde::Deserialize<#de> for #struct_name #type_generics #impl_where {
+                fn deserialize<D__: #serde::Deserializer<#de>>(
+                    deserializer: D__,
+                ) -> ::core::result::Result<Self, D__::Error> {
+                    let #params = <#params_ty as #serde::Deserialize>::deserialize(deserializer)?;
+                    let #result = #build.map_err(|missing| {
+                        <D__::Error as #serde::de::Error>::custom(::std::format!(
+                            "missing fields `{}`",
+                            missing.join("`, `")
+                        ))
+                    })?;
+                    #finish
+                }
+            }
+        };
+    }
+}
@@ -0,0 +1,150 @@
+//! Builders whose parameters are set by name at runtime, for frontends which do not know the types
+//! they are creating at compile time.
+
+use crate::partial::make_runtime_build;
+use crate::{make_generic_args, make_generics_marker, unique_ident, RuntimeBuilder};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::Ident;
+
+/// Generates `<StructName>DynBuilder`, which holds an `Option` for each parameter of the builder
+/// and sets them from their names.
+pub(crate) fn make_dyn_builder(builder: &RuntimeBuilder) -> TokenStream2 {
+    let RuntimeBuilder {
+        vis,
+        struct_name,
+        generics,
+        builder_name,
+        ..
+    } = builder;
+    let dyn_name = format_ident!("{}DynBuilder", struct_name);
+    let name = Ident::new("name", Span::mixed_site());
+    let value = Ident::new("value", Span::mixed_site());
+    let result = Ident::new("result", Span::mixed_site());
+    let mut taken_fields = builder
+        .params
+        .iter()
+        .map(|field| field.borrow_name().to_string())
+        .collect();
+    let marker_field = unique_ident("generics_marker__", &mut taken_fields);
+    let marker = make_generics_marker(generics);
+    let mut field_defs = Vec::new();
+    let mut initial_values = vec![quote! { #marker_field: ::core::marker::PhantomData }];
+    let mut any_arms = Vec::new();
+    let mut str_arms = Vec::new();
+    let mut static_bounds = Vec::new();
+    for field in builder.params {
+        let param = field.borrow_name();
+        let ty = field.borrow_type();
+        let param_str = param.to_string();
+        let ty_str = ty.to_token_stream().to_string();
+        field_defs.push(quote! { #param: ::core::option::Option<#ty> });
+        initial_values.push(quote! { #param: ::core::option::Option::None });
+        any_arms.push(quote! {
+            #param_str => {
+                let #value = #value.downcast::<#ty>().map_err(|_| ::scones::SetError::WrongType {
+                    param: #param_str,
+                    expected: #ty_str,
+                })?;
+                self.#param = ::core::option::Option::Some(*#value);
+                ::core::result::Result::Ok(())
+            }
+        });
+        str_arms.push(quote! {
+            #param_str => {
+                #[allow(unused_imports)]
+                use ::scones::{CannotParseStr__, ParseStr__};
+                let #value = (&::scones::StrParser__::<#ty>::new())
+                    .parse_str__(#value)
+                    .map_err(|message| ::scones::SetError::Invalid {
+                        param: #param_str,
+                        message,
+                    })?;
+                self.#param = ::core::option::Option::Some(#value);
+                ::core::result::Result::Ok(())
+            }
+        });
+        static_bounds.push(quote! { #ty: 'static });
+    }
+    let build = make_runtime_build(builder, |name| quote! { self.#name });
+    let (error, finish) = match builder.error_type {
+        Some(error_type) => (
+            quote! { ::scones::BuildError<#error_type> },
+            quote! { #result.map_err(::scones::BuildError::Failed) },
+        ),
+        None => (
+            quote! { ::scones::BuildError },
+            quote! { ::core::result::Result::Ok(#result) },
+        ),
+    };
+    let generic_args = make_generic_args(generics);
+    let (impl_generics, type_generics, generic_where) = generics.split_for_impl();
+    let doc = format!(
+        "Sets the parameters of `{}` by name instead of through typed setters, so that values \
+        whose types are only known at runtime can be used to create `{}`. Missing required values \
+        are only detected when `build()` is called.",
+        builder_name, struct_name,
+    );
+    quote! {
+        #[doc = #doc]
+        #vis struct #dyn_name #generics #generic_where {
+            #(#field_defs,)*
+            #marker_field: #marker,
+        }
+
+        impl #impl_generics #dyn_name #type_generics #generic_where {
+            /// Creates a builder where nothing has been set.
+            #vis fn new() -> Self {
+                Self {
+                    #(#initial_values),*
+                }
+            }
+
+            /// Sets the parameter called `name`, which must be of the same type as `value`.
+            #vis fn set(
+                &mut self,
+                #name: &str,
+                #value: ::std::boxed::Box<dyn ::core::any::Any>,
+            ) -> ::core::result::Result<(), ::scones::SetError>
+            where
+                #(#static_bounds,)*
+            {
+                match #name {
+                    #(#any_arms)*
+                    _ => ::core::result::Result::Err(
+                        ::scones::SetError::Unknown(::std::borrow::ToOwned::to_owned(#name))
+                    ),
+                }
+            }
+
+            /// Sets the parameter called `name` by parsing `value` with `FromStr`. Parameters
+            /// whose types do not implement `FromStr` always return an error, as do parameters
+            /// whose types are type parameters of the struct.
+            #vis fn set_str(
+                &mut self,
+                #name: &str,
+                #value: &str,
+            ) -> ::core::result::Result<(), ::scones::SetError> {
+                match #name {
+                    #(#str_arms)*
+                    _ => ::core::result::Result::Err(
+                        ::scones::SetError::Unknown(::std::borrow::ToOwned::to_owned(#name))
+                    ),
+                }
+            }
+
+            /// Builds the item, or returns an error listing the required values which were never
+            /// set.
+            #vis fn build(self) -> ::core::result::Result<#struct_name <#(#generic_args),*>, #error> {
+                let #result = #build.map_err(::scones::BuildError::Missing)?;
+                #finish
+            }
+        }
+
+        impl #impl_generics ::core::default::Default for #dyn_name #type_generics #generic_where {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    }
+}
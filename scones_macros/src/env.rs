@@ -0,0 +1,95 @@
+//! Functions which read the values of builder parameters from environment variables named by
+//! `#[env()]` attributes.
+
+use crate::{BuilderField, FieldInfo};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use std::collections::HashSet;
+use syn::parse::ParseStream;
+use syn::{parenthesized, Error, Ident, LitStr, Visibility};
+
+/// Parses the contents of an `#[env()]` attribute.
+pub(crate) fn parse_env(input: ParseStream) -> syn::parse::Result<LitStr> {
+    let content;
+    parenthesized!(content in input);
+    content.parse()
+}
+
+/// Returns an error for the first `#[env()]` attribute whose variable is not read by any of the
+/// generated items, given the names of the parameters whose variables are read.
+pub(crate) fn check_env_read(
+    fields: &[FieldInfo],
+    env_params: &HashSet<String>,
+) -> Result<(), Error> {
+    for field in fields {
+        if let Some(variable) = &field.env {
+            if !env_params.contains(&field.ident.to_string()) {
+                return Err(Error::new_spanned(
+                    variable,
+                    "This variable is never read. Variables are only read for override \
+                    parameters, like `(name?)`, or for any parameter of a builder with the \
+                    partial option.",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generates an `env_overrides()` method which sets each of the given parameters whose field has an
+/// `#[env()]` attribute, as long as the variable is set. Parameters are stored as `Option`s in the
+/// fields of `self` with the same names. Returns nothing if none of the parameters have variables.
+pub(crate) fn make_env_overrides(
+    vis: &Visibility,
+    fields: &[FieldInfo],
+    params: &[&BuilderField],
+) -> TokenStream2 {
+    let errors = Ident::new("errors", Span::mixed_site());
+    let value = Ident::new("value", Span::mixed_site());
+    let mut reads = Vec::new();
+    let mut variables = Vec::new();
+    for param in params {
+        let name = param.borrow_name();
+        let ty = param.borrow_type();
+        let variable = match fields.iter().find(|field| &field.ident == name) {
+            Some(FieldInfo {
+                env: Some(variable),
+                ..
+            }) => variable,
+            _ => continue,
+        };
+        let name_str = name.to_string();
+        reads.push(quote! {
+            let #value = ::scones::read_env__::<#ty>(#variable);
+            if let ::core::option::Option::Some(::core::option::Option::Some(#value)) =
+                #errors.catch(#name_str, #value)
+            {
+                self.#name = ::core::option::Option::Some(#value);
+            }
+        });
+        variables.push(format!("- `{}`: `{}`", variable.value(), name));
+    }
+    if reads.is_empty() {
+        return quote! {};
+    }
+    let doc = format!(
+        "Sets values from the following environment variables, skipping any which are not set:\n{}",
+        variables.join("\n")
+    );
+    quote! {
+        #[doc = #doc]
+        ///
+        /// Returns every variable which could not be parsed with `FromStr` as an error.
+        #vis fn env_overrides(
+            mut self,
+        ) -> ::core::result::Result<Self, ::scones::Errors<::scones::EnvError>> {
+            let mut #errors = ::scones::Errors::new();
+            #(#reads)*
+            if #errors.is_empty() {
+                ::core::result::Result::Ok(self)
+            } else {
+                ::core::result::Result::Err(#errors)
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Factories which build structs filled with placeholder values, for use in tests.
+
+use crate::{
+    make_builder_fields, make_generic_args, make_generics_marker, make_setter_names, BuilderField,
+    BuilderInfo, FieldInfo, ReturnSemantics,
+};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use std::collections::{HashMap, HashSet};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{parenthesized, parse_quote, Error, Expr, Ident, ItemStruct, LitStr, Token, Visibility};
+
+/// The arguments of `#[make_factory]`.
+pub(crate) struct FactoryInfo {
+    vis: Visibility,
+    name: Option<Ident>,
+    /// The builder used to create items, given with `for BuilderName`.
+    builder: Option<Ident>,
+}
+
+impl Parse for FactoryInfo {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let mut vis: Visibility = input.parse()?;
+        let name = if input.peek(Ident) {
+            Some(input.parse()?)
+        } else {
+            // Like builders, factories without an explicit name default to public visibility.
+            vis = parse_quote! { pub };
+            None
+        };
+        let builder = if input.peek(Token![for]) {
+            let _: Token![for] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { vis, name, builder })
+    }
+}
+
+/// The contents of a `#[factory()]` attribute on a field.
+#[allow(clippy::large_enum_variant)]
+enum FactoryDefault {
+    /// `seq = "user{n}"`, which is formatted with the sequence number and then parsed.
+    Sequence(LitStr),
+    Expr(Expr),
+}
+
+impl Parse for FactoryDefault {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let fork = content.fork();
+        let is_sequence = matches!(fork.parse::<Ident>(), Ok(ident) if ident == "seq")
+            && fork.peek(Token![=])
+            && !fork.peek(Token![==]);
+        if !is_sequence {
+            return Ok(Self::Expr(content.parse()?));
+        }
+        let _: Ident = content.parse()?;
+        let _: Token![=] = content.parse()?;
+        let format: LitStr = content.parse()?;
+        if !format.value().contains("{n") {
+            return Err(Error::new_spanned(
+                format,
+                "Sequences must include the sequence number, like \"user{n}\".",
+            ));
+        }
+        Ok(Self::Sequence(format))
+    }
+}
+
+/// Generates a factory for `struct_def`, which creates instances with one of its `builders`. The
+/// builder is given the values from `#[factory()]` attributes on the fields with the same names as
+/// its parameters, and `Default::default()` for any other required parameters.
+pub(crate) fn make_factory_impl(
+    info: FactoryInfo,
+    struct_def: &ItemStruct,
+    builders: &[BuilderInfo],
+    fields: &[FieldInfo],
+) -> Result<TokenStream2, Error> {
+    let struct_name = &struct_def.ident;
+    let generics = &struct_def.generics;
+    let vis = info.vis;
+    let factory_name = info
+        .name
+        .unwrap_or_else(|| format_ident!("{}Factory", struct_name));
+    let builder_name = info
+        .builder
+        .unwrap_or_else(|| format_ident!("{}Builder", struct_name));
+    let builder = builders
+        .iter()
+        .find(|builder| builder.name == builder_name)
+        .ok_or_else(|| {
+            Error::new_spanned(
+                &builder_name,
+                format!(
+                    "make_factory creates items with a builder made by make_builder, but there \
+                    is no builder named {}. Another builder can be chosen with `for`, like \
+                    #[make_factory(pub {} for OtherBuilder)].",
+                    builder_name, factory_name
+                ),
+            )
+        })?;
+    let (_, params) = make_builder_fields(
+        &builder_name.to_string(),
+        builder.params.clone(),
+        fields,
+        &mut HashSet::new(),
+    )?;
+    let setter_names = make_setter_names(&params, &format_ident!("build"));
+
+    let count = Ident::new("count", Span::mixed_site());
+    let customize = Ident::new("customize", Span::mixed_site());
+    let builder_var = Ident::new("builder", Span::mixed_site());
+    let customize_param = Ident::new("F__", Span::mixed_site());
+    // The sequence number can be used by the expressions in `#[factory()]` attributes.
+    let n = Ident::new("n", Span::call_site());
+    let mut factory_values = HashMap::new();
+    for (index, field) in struct_def.fields.iter().enumerate() {
+        let name = field
+            .ident
+            .clone()
+            .unwrap_or_else(|| format_ident!("field_{}", index));
+        for attr in &field.attrs {
+            if !attr.path.is_ident("factory") {
+                continue;
+            }
+            if !params.iter().any(|param| param.borrow_name() == &name) {
+                return Err(Error::new_spanned(
+                    attr,
+                    format!(
+                        "{} is not a parameter of {}, so the factory cannot give it a value.",
+                        name, builder_name
+                    ),
+                ));
+            }
+            let ty = &field.ty;
+            let value = match syn::parse2::<FactoryDefault>(attr.tokens.clone())? {
+                FactoryDefault::Sequence(format) => quote! {
+                    ::scones::sequence__::<#ty>(::std::format!(#format, n = #n))
+                },
+                FactoryDefault::Expr(expr) => quote! { #expr },
+            };
+            factory_values.insert(name.to_string(), value);
+        }
+    }
+    let mut present_args = make_generic_args(generics);
+    let mut setters = Vec::new();
+    for param in &params {
+        let name = param.borrow_name();
+        let setter = &setter_names[&name.to_string()];
+        let value = match (factory_values.get(&name.to_string()), param) {
+            (Some(value), _) => value.clone(),
+            (None, BuilderField::Required { ty, .. }) => {
+                quote_spanned! { ty.span()=> ::core::default::Default::default() }
+            }
+            // Leaving other parameters out lets the builder use their `#[value()]` defaults.
+            (None, _) => continue,
+        };
+        if let BuilderField::Required { .. } = param {
+            present_args.push(quote! { ::scones::Present });
+        }
+        setters.push(quote! { .#setter(#value) });
+    }
+    let builder_ty = quote! { #builder_name <#(#present_args),*> };
+    // The same type as the one returned by the builder's `build()`.
+    let generic_args = make_generic_args(generics);
+    let struct_ty = quote! { #struct_name <#(#generic_args),*> };
+    let result_ty = match (&builder.return_semantics, &builder.custom_return_type) {
+        (ReturnSemantics::Selff, _) | (_, None) => struct_ty,
+        (_, Some(error)) => quote! { ::core::result::Result<#struct_ty, #error> },
+    };
+    let marker_field = Ident::new("generics_marker__", Span::mixed_site());
+    let sequence_field = Ident::new("sequence__", Span::mixed_site());
+    let marker = make_generics_marker(generics);
+    let (impl_generics, type_generics, generic_where) = generics.split_for_impl();
+    let doc = format!(
+        "Builds instances of `{}` with `{}`, filled with placeholder values for use in tests. \
+        Values can be chosen for each instance with `build_with()`, which gives the builder to a \
+        closure before it is built.",
+        struct_name, builder_name
+    );
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #factory_name #generics #generic_where {
+            #marker_field: #marker,
+            #sequence_field: ::core::sync::atomic::AtomicUsize,
+        }
+
+        impl #impl_generics #factory_name #type_generics #generic_where {
+            /// Creates a factory whose sequence starts at 1.
+            #vis fn new() -> Self {
+                Self {
+                    #marker_field: ::core::marker::PhantomData,
+                    #sequence_field: ::core::sync::atomic::AtomicUsize::new(0),
+                }
+            }
+
+            /// Builds an item. Each call uses the next number of this factory's sequence.
+            #vis fn build(&self) -> #result_ty {
+                self.build_with(|#builder_var| #builder_var)
+            }
+
+            /// Builds an item after giving the builder, with the factory's values already set, to
+            /// `customize`. Its setters can be used to choose different values for this item.
+            #vis fn build_with<#customize_param>(&self, #customize: #customize_param) -> #result_ty
+            where
+                #customize_param: ::core::ops::FnOnce(#builder_ty) -> #builder_ty,
+            {
+                #[allow(unused_variables)]
+                let #n: usize = self
+                    .#sequence_field
+                    .fetch_add(1, ::core::sync::atomic::Ordering::Relaxed)
+                    + 1;
+                let #builder_var = #builder_name::new() #(#setters)*;
+                #customize(#builder_var).build()
+            }
+
+            /// Builds `count` items, each with its own sequence number.
+            #vis fn build_many(&self, #count: usize) -> ::std::vec::Vec<#result_ty> {
+                (0..#count).map(|_| self.build()).collect()
+            }
+
+            /// Builds `count` items, giving each of their builders to `customize` like
+            /// `build_with()`.
+            #vis fn build_many_with<#customize_param>(
+                &self,
+                #count: usize,
+                mut #customize: #customize_param,
+            ) -> ::std::vec::Vec<#result_ty>
+            where
+                #customize_param: ::core::ops::FnMut(#builder_ty) -> #builder_ty,
+            {
+                (0..#count).map(|_| self.build_with(&mut #customize)).collect()
+            }
+        }
+
+        impl #impl_generics ::core::default::Default for #factory_name #type_generics #generic_where {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    })
+}
@@ -0,0 +1,486 @@
+//! Builders for functions and methods, which give them named and optional arguments.
+
+use crate::{
+    env, handled_marker, item_attribute_label, make_builder_impl, make_field_info, path_equal,
+    unique_ident, BuilderTarget, GenerateItemsContent, MaybeDocComment, PartialBuilderInfo,
+};
+use inflector::Inflector;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::visit_mut::{self, VisitMut};
+use syn::{
+    parse_quote, Attribute, Error, FnArg, GenericParam, Generics, ImplItem, ItemFn, ItemImpl,
+    Lifetime, LifetimeDef, Lit, LitStr, ParenthesizedGenericArguments, Pat, ReturnType, Signature,
+    Type, TypeBareFn, TypeParam, TypeReference, Visibility,
+};
+
+/// What to do with lifetimes which were left out of a type.
+enum Elision {
+    /// Give each of them a new lifetime parameter, like the compiler does for arguments.
+    Fresh,
+    /// Use the given lifetime, like the compiler does for return types.
+    Use(Lifetime),
+    /// Leave them as they are.
+    Keep,
+}
+
+/// Spells out the parts of a signature which are only implied by where it is written, so that its
+/// types can be used in a builder which lives outside of the function and its impl block.
+struct SignatureFixer<'a> {
+    self_ty: Option<&'a Type>,
+    elision: Elision,
+    taken: HashSet<String>,
+    new_lifetimes: Vec<Lifetime>,
+    /// Parameters standing in for `impl Trait` arguments.
+    new_params: Vec<TypeParam>,
+    /// Every lifetime used by the arguments, used to pick the lifetime of the return type.
+    seen_lifetimes: Vec<Lifetime>,
+}
+
+impl SignatureFixer<'_> {
+    fn elided_lifetime(&mut self) -> Option<Lifetime> {
+        match &self.elision {
+            Elision::Fresh => {
+                let name = unique_ident("lt__", &mut self.taken);
+                let lifetime = Lifetime::new(&format!("'{}", name), name.span());
+                self.new_lifetimes.push(lifetime.clone());
+                Some(lifetime)
+            }
+            Elision::Use(lifetime) => Some(lifetime.clone()),
+            Elision::Keep => None,
+        }
+    }
+
+    /// Lifetimes left out of `Fn(&T)` and `fn(&T)` belong to those types, so they are left alone.
+    fn visit_keeping_elided<F: FnOnce(&mut Self)>(&mut self, visit: F) {
+        let elision = std::mem::replace(&mut self.elision, Elision::Keep);
+        visit(self);
+        self.elision = elision;
+    }
+}
+
+impl VisitMut for SignatureFixer<'_> {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            if let Some(replacement) = self.elided_lifetime() {
+                *lifetime = replacement;
+            }
+        }
+        if let Elision::Fresh = self.elision {
+            self.seen_lifetimes.push(lifetime.clone());
+        }
+    }
+
+    fn visit_type_reference_mut(&mut self, reference: &mut TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = self.elided_lifetime();
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        args: &mut ParenthesizedGenericArguments,
+    ) {
+        self.visit_keeping_elided(|this| {
+            visit_mut::visit_parenthesized_generic_arguments_mut(this, args)
+        });
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, bare_fn: &mut TypeBareFn) {
+        self.visit_keeping_elided(|this| visit_mut::visit_type_bare_fn_mut(this, bare_fn));
+    }
+
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        visit_mut::visit_type_mut(self, ty);
+        match ty {
+            Type::ImplTrait(impl_trait) if matches!(self.elision, Elision::Fresh) => {
+                let name = unique_ident("Arg__", &mut self.taken);
+                let bounds = &impl_trait.bounds;
+                self.new_params.push(parse_quote! { #name: #bounds });
+                *ty = parse_quote! { #name };
+            }
+            Type::Path(path)
+                if path.qself.is_none()
+                    && path.path.leading_colon.is_none()
+                    && path.path.segments[0].ident == "Self" =>
+            {
+                if let Some(self_ty) = self.self_ty {
+                    let rest = path.path.segments.iter().skip(1);
+                    *ty = if path.path.segments.len() == 1 {
+                        self_ty.clone()
+                    } else {
+                        parse_quote! { <#self_ty>#(::#rest)* }
+                    };
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Replaces `Self` in expressions, which would otherwise refer to the builder.
+fn replace_self(tokens: TokenStream2, self_ty: &Type) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => quote! { <#self_ty> },
+            TokenTree::Group(group) => {
+                let mut new_group = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_self(group.stream(), self_ty),
+                );
+                new_group.set_span(group.span());
+                TokenTree::Group(new_group).into()
+            }
+            other => other.into(),
+        })
+        .collect()
+}
+
+fn param_name(param: &GenericParam) -> String {
+    match param {
+        GenericParam::Lifetime(lt) => lt.lifetime.to_string(),
+        GenericParam::Type(tp) => tp.ident.to_string(),
+        GenericParam::Const(cp) => cp.ident.to_string(),
+    }
+}
+
+/// Removes the builder attributes from `attrs`, returning the arguments of each one along with the
+/// `/// ^` comments which belong to it. `invocations` holds the attribute currently being expanded,
+/// if there is one, since the compiler has already removed it from `attrs`.
+fn take_builder_attrs(
+    attrs: &mut Vec<Attribute>,
+    mut invocations: Vec<(TokenStream2, Vec<Lit>)>,
+) -> Result<Vec<(PartialBuilderInfo, Vec<Lit>)>, Error> {
+    let mut kept_attrs = Vec::new();
+    for mut attr in attrs.drain(..) {
+        match item_attribute_label(&attr.path) {
+            Some("builder") => {
+                let args = if attr.tokens.is_empty() {
+                    quote! {}
+                } else {
+                    syn::parse2::<GenerateItemsContent>(attr.tokens)?.args
+                };
+                invocations.push((args, Vec::new()));
+                attr.tokens = handled_marker();
+                kept_attrs.push(attr);
+                continue;
+            }
+            Some(_) => {
+                return Err(Error::new_spanned(
+                    attr,
+                    "Only builders can be made for functions.",
+                ))
+            }
+            None => (),
+        }
+        if path_equal(&attr.path, &parse_quote! { doc }) {
+            if let Ok(MaybeDocComment(Some(content))) = syn::parse2(attr.tokens.clone()) {
+                if let (Some(content), Some((_, documentation))) =
+                    (content.strip_prefix(" ^"), invocations.last_mut())
+                {
+                    documentation.push(Lit::Str(LitStr::new(content, attr.bracket_token.span)));
+                    continue;
+                }
+            }
+        }
+        kept_attrs.push(attr);
+    }
+    *attrs = kept_attrs;
+    invocations
+        .into_iter()
+        .map(|(args, documentation)| Ok((syn::parse2(args)?, documentation)))
+        .collect()
+}
+
+/// Generates the builders for a function, returning them along with functions which start each
+/// builder. For methods, `self_ty` and `impl_generics` describe the impl block they are in.
+fn make_builders(
+    vis: &Visibility,
+    sig: &mut Signature,
+    builders: Vec<(PartialBuilderInfo, Vec<Lit>)>,
+    self_ty: Option<&Type>,
+    impl_generics: &Generics,
+) -> Result<(TokenStream2, Vec<TokenStream2>), Error> {
+    let inherited_params: Vec<_> = impl_generics
+        .params
+        .iter()
+        .chain(&sig.generics.params)
+        .collect();
+    let mut fixer = SignatureFixer {
+        self_ty,
+        elision: Elision::Fresh,
+        taken: inherited_params
+            .iter()
+            .map(|param| param_name(param).trim_start_matches('\'').to_owned())
+            .collect(),
+        new_lifetimes: Vec::new(),
+        new_params: Vec::new(),
+        seen_lifetimes: Vec::new(),
+    };
+
+    // The receiver's type, how the function starting the builder takes it, and the lifetime of
+    // references to it.
+    let mut receiver = None;
+    let mut args = Vec::new();
+    for input in sig.inputs.iter_mut() {
+        let self_ty_for = |span| {
+            self_ty.ok_or_else(|| {
+                Error::new(
+                    span,
+                    "To make a builder for a method, #[make_builder] must also be placed on its \
+                    impl block.",
+                )
+            })
+        };
+        match input {
+            FnArg::Receiver(input) => {
+                let self_ty = self_ty_for(input.self_token.span)?;
+                receiver = Some(match &input.reference {
+                    Some((_, lifetime)) => {
+                        let lifetime = match lifetime {
+                            Some(lifetime) => lifetime.clone(),
+                            None => fixer.elided_lifetime().unwrap(),
+                        };
+                        let mutability = input.mutability;
+                        let ty = parse_quote! { &#lifetime #mutability #self_ty };
+                        let param = quote! { &#lifetime #mutability self };
+                        (ty, param, Some(lifetime))
+                    }
+                    None => (self_ty.clone(), quote! { self }, None),
+                });
+            }
+            FnArg::Typed(input) => {
+                let (value_attrs, other_attrs) =
+                    input.attrs.drain(..).partition(|attr: &Attribute| {
+                        attr.path.is_ident("value")
+                            || attr.path.is_ident("flatten")
+                            || attr.path.is_ident("env")
+                    });
+                input.attrs = other_attrs;
+                let mut ty = (*input.ty).clone();
+                fixer.visit_type_mut(&mut ty);
+                match &*input.pat {
+                    Pat::Ident(pat) if pat.ident == "self" => {
+                        self_ty_for(pat.ident.span())?;
+                        receiver = Some((ty.clone(), quote! { self: #ty }, None));
+                    }
+                    Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                        args.push((pat.ident.clone(), ty, value_attrs));
+                    }
+                    other => {
+                        return Err(Error::new_spanned(
+                            other,
+                            "Functions with builders must give each argument a plain name, \
+                            like `x: i32`.",
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    // Lifetimes left out of the return type are filled in the same way the compiler would.
+    fixer.elision = match &receiver {
+        Some((_, _, Some(lifetime))) => Elision::Use(lifetime.clone()),
+        _ => {
+            let mut seen = fixer.seen_lifetimes.clone();
+            seen.dedup();
+            if seen.len() == 1 {
+                Elision::Use(seen.remove(0))
+            } else {
+                Elision::Keep
+            }
+        }
+    };
+    let mut return_type = match &sig.output {
+        ReturnType::Default => parse_quote! { () },
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    fixer.visit_type_mut(&mut return_type);
+
+    fixer.elision = Elision::Keep;
+    let mut lifetime_params = Vec::new();
+    let mut other_params = Vec::new();
+    for param in &inherited_params {
+        let mut param = (*param).clone();
+        fixer.visit_generic_param_mut(&mut param);
+        match param {
+            GenericParam::Lifetime(_) => lifetime_params.push(param),
+            _ => other_params.push(param),
+        }
+    }
+    let mut predicates = Vec::new();
+    for where_clause in impl_generics
+        .where_clause
+        .iter()
+        .chain(&sig.generics.where_clause)
+    {
+        for predicate in &where_clause.predicates {
+            let mut predicate = predicate.clone();
+            fixer.visit_where_predicate_mut(&mut predicate);
+            predicates.push(predicate);
+        }
+    }
+    for lifetime in fixer.new_lifetimes {
+        lifetime_params.push(GenericParam::Lifetime(LifetimeDef::new(lifetime)));
+    }
+    for param in fixer.new_params {
+        other_params.push(GenericParam::Type(param));
+    }
+    let mut generics = Generics::default();
+    generics.params.extend(lifetime_params);
+    generics.params.extend(other_params);
+    if !predicates.is_empty() {
+        generics.make_where_clause().predicates.extend(predicates);
+    }
+
+    let fn_name = &sig.ident;
+    let pascal_name = format_ident!("{}", fn_name.to_string().to_pascal_case());
+    let mut infos = Vec::new();
+    for (mut info, documentation) in builders {
+        if let Some(ty) = &info.custom_return_type {
+            return Err(Error::new_spanned(
+                ty,
+                "Builders for functions always return what the function returns.",
+            ));
+        }
+        if info.name.is_none() {
+            info.vis = vis.clone();
+        }
+        infos.push(info.complete(&pascal_name, documentation));
+    }
+    let item_names: HashSet<String> = infos.iter().map(|info| info.name.to_string()).collect();
+
+    let mut fields = Vec::new();
+    for (ident, ty, value_attrs) in &args {
+        let mut value_attrs = value_attrs.clone();
+        if let Some(self_ty) = self_ty {
+            for attr in &mut value_attrs {
+                attr.tokens = replace_self(attr.tokens.clone(), self_ty);
+            }
+        }
+        fields.push(make_field_info(
+            ident.clone(),
+            ty,
+            &value_attrs,
+            &item_names,
+        )?);
+    }
+
+    let path = match self_ty {
+        Some(self_ty) => quote! { <#self_ty>::#fn_name },
+        None => quote! { #fn_name },
+    };
+    let target = BuilderTarget::Function {
+        name: fn_name.clone(),
+        path,
+        return_type,
+        self_ty: self_ty.cloned(),
+        receiver: receiver.as_ref().map(|(ty, _, _)| ty.clone()),
+        asyncness: sig.asyncness,
+        unsafety: sig.unsafety,
+    };
+    let (receiver_param, receiver_arg) = match &receiver {
+        Some((_, param, _)) => (param.clone(), quote! { self }),
+        None => (quote! {}, quote! {}),
+    };
+    let impl_param_names: HashSet<String> = impl_generics.params.iter().map(param_name).collect();
+    let mut builder_code = Vec::new();
+    let mut starters = Vec::new();
+    let mut env_params = HashSet::new();
+    for info in infos {
+        let builder_name = info.name.clone();
+        let vis = info.vis.clone();
+        let (code, start) = make_builder_impl(&target, &generics, info, &fields, &mut env_params)?;
+        builder_code.push(code);
+
+        let mut start_generics = start.generics;
+        start_generics.params = start_generics
+            .params
+            .into_iter()
+            .filter(|param| !impl_param_names.contains(&param_name(param)))
+            .collect();
+        let (start_impl_generics, _, start_where) = start_generics.split_for_impl();
+        let start_name = format_ident!("{}", builder_name.to_string().to_snake_case());
+        let start_ty = start.ty;
+        let doc = format!(
+            "Starts a [`{}`], which calls `{}` with named arguments.",
+            builder_name, fn_name
+        );
+        starters.push(quote! {
+            #[doc = #doc]
+            #vis fn #start_name #start_impl_generics (#receiver_param) -> #start_ty #start_where {
+                #builder_name::new(#receiver_arg)
+            }
+        });
+    }
+    env::check_env_read(&fields, &env_params)?;
+    Ok((quote! { #(#builder_code)* }, starters))
+}
+
+pub(crate) fn make_fn_builders(
+    attr: TokenStream2,
+    mut item: ItemFn,
+) -> Result<TokenStream2, Error> {
+    let builders = take_builder_attrs(&mut item.attrs, vec![(attr, Vec::new())])?;
+    let (builder_code, starters) = make_builders(
+        &item.vis,
+        &mut item.sig,
+        builders,
+        None,
+        &Generics::default(),
+    )?;
+    Ok(quote! {
+        #item
+        #builder_code
+        #(#starters)*
+    })
+}
+
+pub(crate) fn make_impl_builders(
+    attr: TokenStream2,
+    mut item: ItemImpl,
+) -> Result<TokenStream2, Error> {
+    if !attr.is_empty() {
+        return Err(Error::new_spanned(
+            attr,
+            "When placed on an impl block, #[make_builder] does not take any arguments. Place it \
+            on the methods which need builders as well.",
+        ));
+    }
+    if let Some((_, path, _)) = &item.trait_ {
+        return Err(Error::new_spanned(
+            path,
+            "Builders can only be made for methods in inherent impl blocks.",
+        ));
+    }
+    let mut builder_code = Vec::new();
+    let mut starters = Vec::new();
+    for impl_item in &mut item.items {
+        if let ImplItem::Method(method) = impl_item {
+            let builders = take_builder_attrs(&mut method.attrs, Vec::new())?;
+            if builders.is_empty() {
+                continue;
+            }
+            let (code, method_starters) = make_builders(
+                &method.vis,
+                &mut method.sig,
+                builders,
+                Some(&item.self_ty),
+                &item.generics,
+            )?;
+            builder_code.push(code);
+            starters.extend(method_starters);
+        }
+    }
+    for starter in starters {
+        item.items.push(syn::parse2(starter)?);
+    }
+    Ok(quote! {
+        #item
+        #(#builder_code)*
+    })
+}
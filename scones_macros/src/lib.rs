@@ -1,14 +1,40 @@
+mod accessors;
+mod accumulate;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod check;
+#[cfg(feature = "cli")]
+mod cli;
+mod describe;
+#[cfg(feature = "serde")]
+mod deserialize;
+mod dynamic;
+mod env;
+#[cfg(feature = "test-factories")]
+mod factory;
+mod function;
+mod matrix;
+mod newtype;
+mod partial;
+#[cfg(feature = "schema")]
+mod schema;
+
+use accessors::AccessorsInfo;
+#[cfg(feature = "test-factories")]
+use factory::FactoryInfo;
 use inflector::Inflector;
+use newtype::NewtypeInfo;
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote};
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 use std::collections::{HashMap, HashSet};
 use syn::parse::{Parse, ParseStream, Parser};
 use syn::punctuated::Punctuated;
 use syn::token::{Comma, Paren};
 use syn::{
-    braced, parenthesized, parse_quote, Attribute, Error, Expr, Fields, GenericParam, Generics,
-    Ident, ItemStruct, Lit, LitStr, Path, Token, Type, Visibility,
+    braced, parenthesized, parse_quote, Attribute, Error, Expr, Field, Fields, GenericParam,
+    Generics, Ident, ItemFn, ItemImpl, ItemStruct, Lit, LitStr, Meta, MetaNameValue, Path, Token,
+    Type, Visibility, WherePredicate,
 };
 
 #[derive(Clone)]
@@ -17,13 +43,98 @@ struct FieldInfo<'a> {
     ty: &'a Type,
     custom_init: HashMap<String, TokenStream2>,
     default_init: Option<TokenStream2>,
+    flatten: Option<Flatten>,
+    checks: Vec<check::Check>,
+    /// The environment variable given by `#[env()]`.
+    env: Option<LitStr>,
+    /// The lines of the field's documentation comments.
+    doc: Vec<String>,
+}
+
+impl FieldInfo<'_> {
+    /// The parameters which stand in for this field when it does not have a value.
+    fn params(&self) -> Vec<(Ident, Type)> {
+        match &self.flatten {
+            Some(flatten) => flatten.params.clone(),
+            None => vec![(self.ident.clone(), self.ty.clone())],
+        }
+    }
+
+    /// The first paragraph of the field's documentation, on a single line.
+    #[cfg(feature = "cli")]
+    fn summary(&self) -> String {
+        let lines: Vec<_> = self
+            .doc
+            .iter()
+            .take_while(|line| !line.is_empty())
+            .map(|line| &line[..])
+            .collect();
+        lines.join(" ")
+    }
+
+    /// The code which initializes this field from its parameters when it does not have a value.
+    fn param_init(&self) -> TokenStream2 {
+        match &self.flatten {
+            Some(Flatten {
+                constructor,
+                params,
+            }) => {
+                let ty = self.ty;
+                let names = params.iter().map(|(name, _)| name);
+                quote! { <#ty>::#constructor(#(#names),*) }
+            }
+            None => {
+                let ident = &self.ident;
+                quote! { #ident }
+            }
+        }
+    }
+}
+
+/// The contents of `#[flatten(constructor(params))]`, which replaces a field with the parameters
+/// of a constructor of its type.
+#[derive(Clone)]
+struct Flatten {
+    constructor: Ident,
+    params: Vec<(Ident, Type)>,
+}
+
+impl Parse for Flatten {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let interior;
+        parenthesized!(interior in input);
+        let constructor: Ident = interior.parse()?;
+        if interior.is_empty() {
+            return Err(Error::new_spanned(
+                constructor,
+                "The parameters of the constructor have to be listed, like \
+                #[flatten(new(x: f32, y: f32))].",
+            ));
+        }
+        let param_list;
+        parenthesized!(param_list in interior);
+        let param_list = param_list.parse_terminated::<_, Comma>(|input: ParseStream| {
+            let name: Ident = input.parse()?;
+            let _: Token![:] = input.parse()?;
+            let ty: Type = input.parse()?;
+            Ok((name, ty))
+        })?;
+        Ok(Self {
+            constructor,
+            params: param_list.into_iter().collect(),
+        })
+    }
 }
 
+#[allow(clippy::large_enum_variant)]
 enum ReturnSemantics {
     Selff,
     Result,
+    /// `Result<Self, scones::Errors<E>>`, which collects the errors of every field. Contains `E`.
+    Accumulate(Type),
 }
 
+#[derive(Clone)]
 #[allow(clippy::large_enum_variant)]
 enum BuilderParam {
     Field {
@@ -64,12 +175,60 @@ impl Parse for BuilderParam {
     }
 }
 
+/// Options which can be listed after the other arguments of `#[make_builder]`, like `, serde`.
+const BUILDER_OPTIONS: &[&str] = &[
+    "serde", "partial", "cli", "dynamic", "schema", "matrix", "untyped",
+];
+
+/// Whether the input starts with one of the given options instead of the usual arguments, like
+/// `#[make_builder(serde)]`.
+fn starts_with_option(input: ParseStream, allowed: &[&str]) -> bool {
+    let fork = input.fork();
+    match fork.parse::<Ident>() {
+        Ok(ident) => {
+            allowed.iter().any(|option| ident == option)
+                && (fork.is_empty() || fork.peek(Token![,]))
+        }
+        Err(..) => false,
+    }
+}
+
+/// Parses the comma-separated options at the end of an item attribute. The first option is not
+/// preceded by a comma when it is the only thing in the attribute.
+fn parse_options(
+    input: ParseStream,
+    allowed: &[&str],
+    leading_comma: bool,
+) -> syn::parse::Result<Vec<Ident>> {
+    let mut options: Vec<Ident> = Vec::new();
+    let mut needs_comma = leading_comma;
+    while !input.is_empty() {
+        if needs_comma {
+            let _: Comma = input.parse()?;
+        }
+        needs_comma = true;
+        let option: Ident = input.parse()?;
+        if !allowed.iter().any(|allowed| option == allowed) {
+            return Err(Error::new_spanned(
+                option,
+                format!("Expected one of the options {}.", allowed.join(", ")),
+            ));
+        }
+        if options.contains(&option) {
+            return Err(Error::new_spanned(option, "This option was already given."));
+        }
+        options.push(option);
+    }
+    Ok(options)
+}
+
 struct PartialBuilderInfo {
     vis: Visibility,
     name: Option<Ident>,
     params: Vec<BuilderParam>,
     custom_return_type: Option<Type>,
     return_semantics: ReturnSemantics,
+    options: Vec<Ident>,
 }
 
 struct BuilderInfo {
@@ -78,17 +237,25 @@ struct BuilderInfo {
     params: Vec<BuilderParam>,
     custom_return_type: Option<Type>,
     return_semantics: ReturnSemantics,
+    options: Vec<Ident>,
     documentation: Vec<Lit>,
 }
 
 impl PartialBuilderInfo {
+    fn final_name(&self, struct_name: &Ident) -> Ident {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format_ident!("{}Builder", struct_name))
+    }
+
     fn complete(self, struct_name: &Ident, documentation: Vec<Lit>) -> BuilderInfo {
         BuilderInfo {
+            name: self.final_name(struct_name),
             vis: self.vis,
-            name: self.name.unwrap_or(format_ident!("{}Builder", struct_name)),
             params: self.params,
             custom_return_type: self.custom_return_type,
             return_semantics: self.return_semantics,
+            options: self.options,
             documentation,
         }
     }
@@ -96,6 +263,16 @@ impl PartialBuilderInfo {
 
 impl Parse for PartialBuilderInfo {
     fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        if starts_with_option(input, BUILDER_OPTIONS) {
+            return Ok(Self {
+                vis: parse_quote! { pub },
+                name: None,
+                params: Vec::new(),
+                custom_return_type: None,
+                return_semantics: ReturnSemantics::Selff,
+                options: parse_options(input, BUILDER_OPTIONS, false)?,
+            });
+        }
         // An empty input is also a visibility.
         let mut vis: Visibility = input.parse().unwrap();
         let name: Option<Ident> = if input.peek(Ident) {
@@ -127,8 +304,12 @@ impl Parse for PartialBuilderInfo {
                 let _: Token![,] = fork.parse()?;
                 let other_type: Type = fork.parse()?;
                 let _: Token![>] = fork.parse()?;
+                let semantics = match accumulate::accumulated_error(&other_type) {
+                    Some(error_type) => ReturnSemantics::Accumulate(error_type.clone()),
+                    None => ReturnSemantics::Result,
+                };
                 ty = other_type;
-                ReturnSemantics::Result
+                semantics
             } else {
                 return Err(Error::new_spanned(
                     ty,
@@ -139,12 +320,14 @@ impl Parse for PartialBuilderInfo {
         } else {
             (None, ReturnSemantics::Selff)
         };
+        let options = parse_options(input, BUILDER_OPTIONS, true)?;
         Ok(Self {
             vis,
             name,
             params,
             custom_return_type,
             return_semantics,
+            options,
         })
     }
 }
@@ -181,13 +364,58 @@ impl Parse for ConstructorParam {
 struct ConstructorInfo {
     vis: Visibility,
     name: Ident,
+    /// Extra generic parameters and where clause belonging to the constructor itself.
+    generics: Generics,
     params: Vec<ConstructorParam>,
     custom_return_type: Option<Type>,
     return_semantics: ReturnSemantics,
+    /// Another constructor which this one forwards to instead of initializing fields itself.
+    delegate: Option<Delegate>,
+    /// A function which takes the struct apart into the parameters of the constructor.
+    inverse: Option<(Visibility, Ident)>,
+    options: Vec<Ident>,
+}
+
+/// The `= other(args)` part of a constructor which forwards to another constructor.
+struct Delegate {
+    target: Ident,
+    args: Punctuated<Expr, Comma>,
+    question: Option<Token![?]>,
+}
+
+impl Parse for Delegate {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let target = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let args = content.parse_terminated(Expr::parse)?;
+        let question = input.parse()?;
+        Ok(Self {
+            target,
+            args,
+            question,
+        })
+    }
 }
 
+/// Options which can be listed after the other arguments of `#[make_constructor]`.
+const CONSTRUCTOR_OPTIONS: &[&str] = &["arbitrary"];
+
 impl Parse for ConstructorInfo {
     fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        if starts_with_option(input, CONSTRUCTOR_OPTIONS) {
+            return Ok(Self {
+                vis: parse_quote! { pub },
+                name: parse_quote! { new },
+                generics: Generics::default(),
+                params: vec![ConstructorParam::Ellipses],
+                custom_return_type: None,
+                return_semantics: ReturnSemantics::Selff,
+                delegate: None,
+                inverse: None,
+                options: parse_options(input, CONSTRUCTOR_OPTIONS, false)?,
+            });
+        }
         // An empty input is also a visibility.
         let mut vis: Visibility = input.parse().unwrap();
         let name: Ident = if input.peek(Ident) {
@@ -197,7 +425,9 @@ impl Parse for ConstructorInfo {
             vis = parse_quote! { pub };
             parse_quote! { new }
         };
-        let params = if input.peek(Paren) {
+        let mut generics: Generics = input.parse()?;
+        let explicit_params = input.peek(Paren);
+        let mut params = if explicit_params {
             let content;
             parenthesized!(content in input);
             let param_list = content.parse_terminated::<_, Comma>(ConstructorParam::parse)?;
@@ -219,9 +449,13 @@ impl Parse for ConstructorInfo {
                 let _: Token![,] = fork.parse()?;
                 let other_type: Type = fork.parse()?;
                 let _: Token![>] = fork.parse()?;
+                let semantics = match accumulate::accumulated_error(&other_type) {
+                    Some(error_type) => ReturnSemantics::Accumulate(error_type.clone()),
+                    None => ReturnSemantics::Result,
+                };
                 // Make sure we are using the right Result type.
                 ty = parse_quote! { ::core::result::Result<Self, #other_type> };
-                ReturnSemantics::Result
+                semantics
             } else {
                 return Err(Error::new_spanned(
                     ty,
@@ -232,12 +466,34 @@ impl Parse for ConstructorInfo {
         } else {
             (None, ReturnSemantics::Selff)
         };
+        generics.where_clause = input.parse()?;
+        let delegate = if input.peek(Token![=]) && !input.peek(Token![=>]) {
+            let _: Token![=] = input.parse()?;
+            // Constructors which forward to another one do not take any fields by default.
+            if !explicit_params {
+                params.clear();
+            }
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        let inverse = if input.peek(Token![=>]) {
+            let _: Token![=>] = input.parse()?;
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        let options = parse_options(input, CONSTRUCTOR_OPTIONS, true)?;
         Ok(Self {
             vis,
             name,
+            generics,
             params,
             custom_return_type,
             return_semantics,
+            delegate,
+            inverse,
+            options,
         })
     }
 }
@@ -267,60 +523,92 @@ impl BuilderField {
             | Self::Override { name, .. } => name,
         }
     }
+
+    fn borrow_type(&self) -> &Type {
+        match self {
+            Self::Required { ty, .. } | Self::Optional { ty, .. } | Self::Override { ty, .. } => ty,
+        }
+    }
+}
+
+/// Picks a name starting with `base` which is not in `taken` and marks it as taken. Used for
+/// identifiers which share a namespace with names chosen by the user, like generic parameters and
+/// builder fields, where hygiene cannot keep them apart.
+fn unique_ident(base: &str, taken: &mut HashSet<String>) -> Ident {
+    let mut name = base.to_owned();
+    while taken.contains(&name) {
+        name.push('_');
+    }
+    taken.insert(name.clone());
+    Ident::new(&name, Span::call_site())
 }
 
 fn make_builder_fields(
     builder_name: &str,
     params: Vec<BuilderParam>,
     fields: &[FieldInfo],
+    taken_generics: &mut HashSet<String>,
 ) -> Result<(Vec<Ident>, Vec<BuilderField>), Error> {
+    let mut make_status_param = |name: &Ident| {
+        unique_ident(
+            &format!("{}Status__", name.to_string().to_pascal_case()),
+            taken_generics,
+        )
+    };
     let mut status_params = Vec::new();
     let mut builder_fields = Vec::new();
     // Stores fields that must be in the parameters of the builder but the user has not
     // yet explicitly specified any extra settings for them.
     let mut remaining_fields: Vec<_> = fields
         .iter()
-        .cloned()
         .filter(|e| !e.custom_init.contains_key(builder_name) && e.default_init.is_none())
+        .cloned()
         .collect();
     for param in params {
         match param {
             BuilderParam::Field { name, overrid } => {
-                let mut found_field: Option<FieldInfo> = None;
+                // Also stores whether the field has a value for this builder.
+                let mut found_field: Option<(FieldInfo, bool)> = None;
                 for (index, field) in remaining_fields.iter().enumerate() {
                     if field.ident == name {
-                        found_field = Some(remaining_fields.remove(index));
+                        found_field = Some((remaining_fields.remove(index), false));
                         break;
                     }
                 }
                 if found_field.is_none() {
                     for field in fields {
                         if field.ident == name {
-                            found_field = Some(field.clone());
+                            found_field = Some((field.clone(), true));
                             break;
                         }
                     }
                 }
-                if let Some(field) = found_field {
+                if let Some((field, has_value)) = found_field {
                     if overrid {
                         builder_fields.push(BuilderField::Override {
                             name,
                             ty: field.ty.clone(),
                         })
                     } else {
-                        let status_param =
-                            format_ident!("{}Status__", field.ident.to_string().to_pascal_case());
-                        status_params.push(status_param.clone());
-                        builder_fields.push(BuilderField::Required {
-                            name,
-                            ty: field.ty.clone(),
-                            status_param,
-                        })
+                        let params = if has_value {
+                            vec![(name, field.ty.clone())]
+                        } else {
+                            field.params()
+                        };
+                        for (name, ty) in params {
+                            let status_param = make_status_param(&name);
+                            status_params.push(status_param.clone());
+                            builder_fields.push(BuilderField::Required {
+                                name,
+                                ty,
+                                status_param,
+                            })
+                        }
                     }
                 } else {
                     return Err(Error::new_spanned(
                         name,
-                        concat!("Could not find a field with this name",),
+                        "Could not find a field with this name",
                     ));
                 }
             }
@@ -328,8 +616,7 @@ fn make_builder_fields(
                 if optional {
                     builder_fields.push(BuilderField::Optional { name, ty });
                 } else {
-                    let status_param =
-                        format_ident!("{}Status__", name.to_string().to_pascal_case());
+                    let status_param = make_status_param(&name);
                     status_params.push(status_param.clone());
                     builder_fields.push(BuilderField::Required {
                         name,
@@ -341,43 +628,251 @@ fn make_builder_fields(
         }
     }
     for field in remaining_fields {
-        let status_param = format_ident!("{}Status__", field.ident.to_string().to_pascal_case());
-        status_params.push(status_param.clone());
-        builder_fields.push(BuilderField::Required {
-            name: field.ident,
-            ty: field.ty.clone(),
-            status_param,
-        })
+        for (name, ty) in field.params() {
+            let status_param = make_status_param(&name);
+            status_params.push(status_param.clone());
+            builder_fields.push(BuilderField::Required {
+                name,
+                ty,
+                status_param,
+            })
+        }
     }
     Ok((status_params, builder_fields))
 }
 
+fn tokens_mention(tokens: TokenStream2, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(other) => &other == ident,
+        TokenTree::Group(group) => tokens_mention(group.stream(), ident),
+        _ => false,
+    })
+}
+
+fn type_is_param(ty: &Type, param: &Ident) -> bool {
+    if let Type::Path(path) = ty {
+        path.qself.is_none() && path.path.is_ident(param)
+    } else {
+        false
+    }
+}
+
+/// Finds type parameters of the struct which can be changed by calling the setter of a required
+/// field, returning a map from the name of that field to the parameter it changes. This is only
+/// possible when the field is the only one in the builder which mentions the parameter, its type
+/// is exactly the parameter, and the only bounds involving the parameter are bounds on the
+/// parameter itself. Those bounds can then be left off the builder until `build()` is called.
+fn find_changeable_params(
+    generic_params: &Generics,
+    builder_fields: &[BuilderField],
+) -> HashMap<String, Ident> {
+    let mut changeable = HashMap::new();
+    'params: for param in &generic_params.params {
+        let param = if let GenericParam::Type(tp) = param {
+            &tp.ident
+        } else {
+            continue;
+        };
+        for other in &generic_params.params {
+            let mentioned = match other {
+                GenericParam::Type(tp) if &tp.ident != param => {
+                    tokens_mention(quote! { #tp }, param)
+                }
+                GenericParam::Const(cp) => tokens_mention(quote! { #cp }, param),
+                _ => false,
+            };
+            if mentioned {
+                continue 'params;
+            }
+        }
+        if let Some(where_clause) = &generic_params.where_clause {
+            for predicate in &where_clause.predicates {
+                if !tokens_mention(quote! { #predicate }, param) {
+                    continue;
+                }
+                match predicate {
+                    WherePredicate::Type(pt)
+                        if pt.lifetimes.is_none() && type_is_param(&pt.bounded_ty, param) => {}
+                    _ => continue 'params,
+                }
+            }
+        }
+        let mut mentioned_by = builder_fields
+            .iter()
+            .filter(|field| tokens_mention(field.borrow_type().to_token_stream(), param));
+        if let (Some(BuilderField::Required { name, ty, .. }), None) =
+            (mentioned_by.next(), mentioned_by.next())
+        {
+            if type_is_param(ty, param) {
+                changeable.insert(name.to_string(), param.clone());
+            }
+        }
+    }
+    changeable
+}
+
+/// Replaces the argument for the struct parameter `param` with `replacement`.
+fn replace_generic_arg(
+    args: &[TokenStream2],
+    param: &Ident,
+    replacement: TokenStream2,
+) -> Vec<TokenStream2> {
+    let param = quote! { #param }.to_string();
+    args.iter()
+        .map(|arg| {
+            if arg.to_string() == param {
+                replacement.clone()
+            } else {
+                arg.clone()
+            }
+        })
+        .collect()
+}
+
+/// What a builder produces once all of its required values have been provided.
+#[allow(clippy::large_enum_variant)]
+enum BuilderTarget {
+    /// An instance of the struct the builder was generated for.
+    Struct { name: Ident, is_tuple: bool },
+    /// The result of calling a function with the builder's values as arguments.
+    Function {
+        name: Ident,
+        /// The path used to call the function, like `connect` or `<Server>::connect`.
+        path: TokenStream2,
+        return_type: Type,
+        /// The type of the impl block the function is in, whose parameters are decided as soon as
+        /// the builder is started.
+        self_ty: Option<Type>,
+        /// The type of `self` if the function is a method. It is passed to the builder's `new()`.
+        receiver: Option<Type>,
+        asyncness: Option<Token![async]>,
+        unsafety: Option<Token![unsafe]>,
+    },
+}
+
+/// The parts of a builder for a struct which are needed to generate code that uses the builder at
+/// runtime, like the partial values made by the `partial` option.
+struct RuntimeBuilder<'a> {
+    vis: &'a Visibility,
+    struct_name: &'a Ident,
+    generics: &'a Generics,
+    builder_name: &'a Ident,
+    params: &'a [BuilderField],
+    setter_names: &'a HashMap<String, Ident>,
+    /// The error type of the builder, if it is fallible.
+    error_type: Option<&'a Type>,
+    fields: &'a [FieldInfo<'a>],
+}
+
+/// Returns the name of the setter for each parameter of a builder. Setters for parameters named
+/// `new` or after `finish`, the function which completes the builder, would clash with the
+/// builder's own functions, so they are given trailing underscores instead.
+fn make_setter_names(fields: &[BuilderField], finish: &Ident) -> HashMap<String, Ident> {
+    let mut taken_fns: HashSet<String> = fields
+        .iter()
+        .map(|field| field.borrow_name().to_string())
+        .collect();
+    taken_fns.insert("new".to_owned());
+    taken_fns.insert(finish.to_string());
+    fields
+        .iter()
+        .map(|field| {
+            let name = field.borrow_name();
+            let setter = if name == "new" || name == finish {
+                unique_ident(&format!("{}_", name), &mut taken_fns)
+            } else {
+                name.clone()
+            };
+            (name.to_string(), setter)
+        })
+        .collect()
+}
+
+/// The signature of a builder's `new()` function, so that other functions can start the builder.
+struct BuilderStart {
+    generics: Generics,
+    ty: TokenStream2,
+}
+
+/// Generates a builder and its options. The names of the parameters whose `#[env()]` variables
+/// are read by the generated code are added to `env_params`.
 fn make_builder_impl(
-    struct_name: &Ident,
-    is_tuple: bool,
+    target: &BuilderTarget,
     generic_params: &Generics,
     info: BuilderInfo,
     fields: &[FieldInfo],
-) -> Result<TokenStream2, Error> {
+    env_params: &mut HashSet<String>,
+) -> Result<(TokenStream2, BuilderStart), Error> {
     let builder_name = info.name;
     let str_name = builder_name.to_string();
-    let (status_params, builder_fields) = make_builder_fields(&str_name, info.params, fields)?;
+    let mut taken_generics: HashSet<String> = generic_params
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(tp) => Some(tp.ident.to_string()),
+            GenericParam::Const(cp) => Some(cp.ident.to_string()),
+            GenericParam::Lifetime(..) => None,
+        })
+        .collect();
+    let (status_params, builder_fields) =
+        make_builder_fields(&str_name, info.params, fields, &mut taken_generics)?;
     let all_fields = builder_fields.clone();
+    // Setters only change type parameters when the builder opts in with the untyped option, so
+    // that new() stays generic over every parameter otherwise.
+    let mut changeable_params = if info.options.iter().any(|option| option == "untyped") {
+        find_changeable_params(generic_params, &builder_fields)
+    } else {
+        HashMap::new()
+    };
+    if let BuilderTarget::Function {
+        self_ty: Some(ty), ..
+    } = target
+    {
+        changeable_params.retain(|_, param| !tokens_mention(ty.to_token_stream(), param));
+    }
     let vis = info.vis;
     let generic_args = make_generic_args(generic_params);
-    let mut field_defs = Vec::new();
-    let mut initial_values = Vec::new();
+    let generics_marker = make_generics_marker(generic_params);
+    let mut taken_fields: HashSet<String> = all_fields
+        .iter()
+        .map(|field| field.borrow_name().to_string())
+        .collect();
+    let marker_field = unique_ident("generics_marker__", &mut taken_fields);
+    let finish = match target {
+        BuilderTarget::Struct { .. } => format_ident!("build"),
+        BuilderTarget::Function { .. } => format_ident!("call"),
+    };
+    let setter_names = make_setter_names(&all_fields, &finish);
+    let value = Ident::new("value", Span::mixed_site());
+    let mut field_defs = vec![quote! { #marker_field: #generics_marker }];
+    let mut initial_values = vec![quote! { #marker_field: ::core::marker::PhantomData }];
     let mut field_mutators = Vec::new();
     let mut constructor_setup = Vec::new();
     let mut override_fields = HashSet::new();
+    // Methods keep their receiver in the builder until they are called.
+    let receiver = Ident::new("receiver", Span::mixed_site());
+    let mut receiver_field = None;
+    let mut new_params = quote! {};
+    if let BuilderTarget::Function {
+        receiver: Some(ty), ..
+    } = target
+    {
+        let field = unique_ident("receiver__", &mut taken_fields);
+        field_defs.push(quote! { #field: #ty });
+        initial_values.push(quote! { #field: #receiver });
+        constructor_setup.push(quote! { let #receiver = self.#field; });
+        new_params = quote! { #receiver: #ty };
+        receiver_field = Some(field);
+    }
     for field in builder_fields {
+        let setter = &setter_names[&field.borrow_name().to_string()];
         match field {
             BuilderField::Optional { name, ty } => {
                 field_defs.push(quote! { #name: ::std::option::Option<#ty> });
                 initial_values.push(quote! { #name: ::std::option::Option::None });
                 field_mutators.push(quote! {
-                    #vis fn #name(mut self, value: #ty) -> Self {
-                        self.#name = ::std::option::Option::Some(value);
+                    #vis fn #setter(mut self, #value: #ty) -> Self {
+                        self.#name = ::std::option::Option::Some(#value);
                         self
                     }
                 });
@@ -387,8 +882,8 @@ fn make_builder_impl(
                 field_defs.push(quote! { #name: ::std::option::Option<#ty> });
                 initial_values.push(quote! { #name: ::std::option::Option::None });
                 field_mutators.push(quote! {
-                    #vis fn #name(mut self, value: #ty) -> Self {
-                        self.#name = ::std::option::Option::Some(value);
+                    #vis fn #setter(mut self, #value: #ty) -> Self {
+                        self.#name = ::std::option::Option::Some(#value);
                         self
                     }
                 });
@@ -414,22 +909,36 @@ fn make_builder_impl(
                         }
                     })
                     .collect();
-                let mut new_generic_args = generic_args.clone();
+                let changed_param = changeable_params.get(&name.to_string());
+                let (mut new_generic_args, ty, setter_params) = if let Some(param) = changed_param {
+                    let new_param = unique_ident(&format!("New{}__", param), &mut taken_generics);
+                    let args = replace_generic_arg(&generic_args, param, quote! { #new_param });
+                    (args, parse_quote! { #new_param }, quote! { <#new_param> })
+                } else {
+                    (generic_args.clone(), ty, quote! {})
+                };
                 new_generic_args.append(&mut sp_after_mut);
-                let mut mutator_fields = Vec::new();
+                let mut mutator_fields = vec![quote! {
+                    #marker_field: ::core::marker::PhantomData
+                }];
+                if let Some(field) = &receiver_field {
+                    mutator_fields.push(quote! { #field: self.#field });
+                }
                 for other_field in &all_fields {
                     let other_name = other_field.borrow_name();
                     // If this is the field we are mutating...
                     if other_name == &name {
                         mutator_fields.push(
-                            quote! { #name: ::scones::BuilderFieldContainer::present(value) },
+                            quote! { #name: ::scones::BuilderFieldContainer::present(#value) },
                         );
                     } else {
                         mutator_fields.push(quote! { #other_name: self.#other_name });
                     }
                 }
                 field_mutators.push(quote! {
-                    #vis fn #name(self, value: #ty) -> #builder_name <#(#new_generic_args),*> {
+                    #vis fn #setter #setter_params(self, #value: #ty)
+                        -> #builder_name <#(#new_generic_args),*>
+                    {
                         #builder_name {
                             #(#mutator_fields),*
                         }
@@ -440,33 +949,55 @@ fn make_builder_impl(
         }
     }
 
+    let override_params: Vec<_> = all_fields
+        .iter()
+        .filter(|field| matches!(field, BuilderField::Override { .. }))
+        .collect();
+    field_mutators.push(env::make_env_overrides(&vis, fields, &override_params));
+    // Partial values read variables for every parameter.
+    let env_readers = if info.options.iter().any(|option| option == "partial") {
+        all_fields.iter().collect()
+    } else {
+        override_params
+    };
+    env_params.extend(
+        env_readers
+            .iter()
+            .map(|field| field.borrow_name().to_string()),
+    );
+
+    let mut inits = Vec::new();
     let mut initializers = Vec::new();
     for field in fields {
         let ident = &field.ident;
-        let init = field
+        let mut init = field
             .custom_init
             .get(&str_name)
-            .or_else(|| field.default_init.as_ref())
+            .or(field.default_init.as_ref())
             .cloned()
-            .unwrap_or(quote! { #ident });
-        let prefix = if is_tuple {
-            quote! {}
-        } else {
-            quote! { #ident: }
-        };
+            .unwrap_or_else(|| field.param_init());
         if override_fields.contains(&ident.to_string()) {
-            initializers.push(quote! {
-                #prefix #ident.unwrap_or(#init)
-            });
-        } else {
-            initializers.push(quote! {
-                #prefix #init
-            });
+            init = quote! { #ident.unwrap_or(#init) };
         }
+        let prefix = match target {
+            BuilderTarget::Struct {
+                is_tuple: false, ..
+            } => quote! { #ident: },
+            _ => quote! {},
+        };
+        initializers.push(quote! {
+            #prefix #init
+        });
+        inits.push(init);
     }
 
+    // Parameters which can be changed by a setter start out as a placeholder.
+    let mut initial_generic_args = generic_args.clone();
+    for param in changeable_params.values() {
+        initial_generic_args = replace_generic_arg(&initial_generic_args, param, quote! { () });
+    }
     let all_missing_args = {
-        let mut vec = generic_args.clone();
+        let mut vec = initial_generic_args;
         vec.append(
             &mut status_params
                 .iter()
@@ -490,92 +1021,252 @@ fn make_builder_impl(
         vec.append(&mut status_params.iter().map(|i| quote! { #i }).collect());
         vec
     };
-    let result_type: Type = parse_quote! { #struct_name <#(#generic_args),*> };
+    let (result_type, make_result, finish_qualifiers) = match target {
+        BuilderTarget::Struct { name, is_tuple } => {
+            let result_type: Type = parse_quote! { #name <#(#generic_args),*> };
+            let make_result = if *is_tuple {
+                quote! { #name ( #(#initializers),* ) }
+            } else {
+                quote! { #name { #(#initializers),* } }
+            };
+            (result_type, make_result, quote! {})
+        }
+        BuilderTarget::Function {
+            path,
+            return_type,
+            asyncness,
+            unsafety,
+            ..
+        } => {
+            let receiver = receiver_field.as_ref().map(|_| quote! { #receiver, });
+            let mut call = quote! { #path(#receiver #(#initializers),*) };
+            if asyncness.is_some() {
+                call = quote! { #call.await };
+            }
+            if unsafety.is_some() {
+                call = quote! { #[allow(unused_unsafe)] unsafe { #call } };
+            }
+            (return_type.clone(), call, quote! { #asyncness #unsafety })
+        }
+    };
+    let error_type = info.custom_return_type.clone();
     let mut return_type = info
         .custom_return_type
         .unwrap_or_else(|| result_type.clone());
     let return_semantics = info.return_semantics;
-    let make_result = if is_tuple {
-        quote! { #struct_name ( #(#initializers),* ) }
-    } else {
-        quote! { #struct_name { #(#initializers),* } }
-    };
-    let constructor_body = match return_semantics {
-        ReturnSemantics::Selff => make_result,
-        ReturnSemantics::Result => {
-            return_type = parse_quote! { ::core::result::Result<#result_type, #return_type> };
-            quote! { ::core::result::Result::Ok(#make_result) }
+    let fallible = !matches!(return_semantics, ReturnSemantics::Selff);
+    if fallible {
+        return_type = parse_quote! { ::core::result::Result<#result_type, #return_type> };
+    }
+    let mut track_caller = quote! {};
+    let constructor_body = match (target, &return_semantics) {
+        (BuilderTarget::Struct { name, is_tuple }, ReturnSemantics::Accumulate(error_type)) => {
+            accumulate::make_accumulating(
+                name,
+                fields,
+                *is_tuple,
+                inits,
+                quote! { #name },
+                error_type,
+            )
+        }
+        (BuilderTarget::Struct { name, is_tuple }, _) if check::has_checks(fields) => {
+            if !fallible {
+                track_caller = quote! { #[track_caller] };
+            }
+            check::make_checked(name, fields, *is_tuple, make_result, fallible)
         }
+        _ if fallible => quote! { ::core::result::Result::Ok(#make_result) },
+        _ => make_result,
     };
-    let generic_where = &generic_params.where_clause;
-    let mut all_generic_params = generic_params.clone();
+    let (impl_generics, _, generic_where) = generic_params.split_for_impl();
+    // Until build() is called, the builder does not require any bounds on parameters which can be
+    // changed by setters.
+    let mut unbounded_params = generic_params.clone();
+    for param in unbounded_params.params.iter_mut() {
+        if let GenericParam::Type(tp) = param {
+            if changeable_params
+                .values()
+                .any(|changeable| changeable == &tp.ident)
+            {
+                tp.colon_token = None;
+                tp.bounds.clear();
+            }
+        }
+    }
+    if let Some(where_clause) = &mut unbounded_params.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| {
+                let predicate = quote! { #predicate };
+                !changeable_params
+                    .values()
+                    .any(|param| tokens_mention(predicate.clone(), param))
+            })
+            .cloned()
+            .collect();
+    }
+    let unbounded_where = &unbounded_params.where_clause;
+    let mut new_generic_params = unbounded_params.clone();
+    new_generic_params.params = new_generic_params
+        .params
+        .iter()
+        .filter(|param| match param {
+            GenericParam::Type(tp) => !changeable_params.values().any(|p| p == &tp.ident),
+            _ => true,
+        })
+        .cloned()
+        .collect();
+    let (new_impl_generics, _, _) = new_generic_params.split_for_impl();
+    // The status params are given defaults so that they can follow struct params which have
+    // defaults of their own.
+    let mut builder_generic_params = unbounded_params.clone();
+    let mut all_generic_params = unbounded_params.clone();
     for status_param in &status_params {
+        builder_generic_params
+            .params
+            .push(parse_quote! { #status_param: ::scones::FieldStatus = ::scones::Missing });
         all_generic_params
             .params
-            .push(parse_quote! { #status_param });
+            .push(parse_quote! { #status_param: ::scones::FieldStatus });
     }
+    let (all_impl_generics, _, _) = all_generic_params.split_for_impl();
 
     let mut documentation = "".to_owned();
+    match target {
+        BuilderTarget::Struct { name, .. } => {
+            documentation.push_str(&format!(
+                "A builder which creates an instance of `{}`. \n\nUse `{}::new()` to start the builder. ",
+                name, builder_name,
+            ));
+            documentation.push_str("Calling `build()` consumes the builder, returning the ");
+            documentation.push_str("completed item. ");
+        }
+        BuilderTarget::Function { name, .. } => {
+            documentation.push_str(&format!(
+                "A builder which calls `{}`. \n\nUse `{}::new()` to start the builder. ",
+                name, builder_name,
+            ));
+            documentation.push_str("Calling `call()` consumes the builder, returning the result ");
+            documentation.push_str("of the function. ");
+        }
+    }
+    documentation.push_str(&format!(
+        "Before calling `{}()`, you can modify values the builder will use by calling any of the ",
+        finish
+    ));
+    documentation.push_str("other functions. For this builder, you must call all of the ");
     documentation.push_str(&format!(
-        "A builder which creates an instance of `{}`. \n\nUse `{}::new()` to start the builder. ",
-        struct_name, builder_name,
+        "following functions at least once before calling `{}()`, or you will receive a ",
+        finish
     ));
-    documentation.push_str("Calling `build()` consumes the builder, returning the completed ");
-    documentation.push_str("item. Before calling `build()`, you can modify values the builder ");
-    documentation.push_str("will use by calling any of the other functions. For this builder, ");
-    documentation.push_str("you must call all of the following functions at least once before ");
-    documentation.push_str("calling `build()`, or you will receive a compilation error:\n");
-    let mut example = format!("");
+    documentation.push_str("compilation error:\n");
+    let mut example = String::new();
     for field in &all_fields {
         if let BuilderField::Required { name, ty, .. } = field {
-            documentation.push_str(&format!("- `{}(value: {})`\n", name, quote! { #ty }));
-            example.push_str(&format!("\n    .{}(value)", name));
+            let setter = &setter_names[&name.to_string()];
+            documentation.push_str(&format!("- `{}(value: {})`\n", setter, quote! { #ty }));
+            example.push_str(&format!("\n    .{}(value)", setter));
         }
     }
     documentation.push_str("\nHere is a minimal example:\n```ingore\n");
     documentation.push_str(&format!(
-        "let instance = {}::new(){}.build();\n```",
-        builder_name, example,
+        "let instance = {}::new(){}.{}();\n```",
+        builder_name, example, finish,
     ));
     let user_doc = info.documentation;
+    let runtime = match target {
+        BuilderTarget::Struct { name, .. } => Some(RuntimeBuilder {
+            vis: &vis,
+            struct_name: name,
+            generics: generic_params,
+            builder_name: &builder_name,
+            params: &all_fields,
+            setter_names: &setter_names,
+            error_type: error_type.as_ref().filter(|_| fallible),
+            fields,
+        }),
+        BuilderTarget::Function { .. } => None,
+    };
+    let mut option_items = Vec::new();
+    for option in info.options.iter().filter(|option| *option != "untyped") {
+        let runtime = runtime.as_ref().ok_or_else(|| {
+            Error::new_spanned(
+                option,
+                format!(
+                    "The {} option can only be used on builders for structs.",
+                    option
+                ),
+            )
+        })?;
+        option_items.push(match &option.to_string()[..] {
+            #[cfg(feature = "serde")]
+            "serde" => deserialize::make_deserialize_impl(runtime),
+            "partial" => partial::make_partial(runtime),
+            "dynamic" => dynamic::make_dyn_builder(runtime),
+            "matrix" => matrix::make_matrix(runtime),
+            #[cfg(feature = "schema")]
+            "schema" => schema::make_schema_impl(runtime),
+            #[cfg(feature = "cli")]
+            "cli" => cli::make_cli_impl(runtime),
+            _ => {
+                return Err(Error::new_spanned(
+                    option,
+                    format!(
+                        "The {} option requires the {} feature of scones to be enabled.",
+                        option, option
+                    ),
+                ))
+            }
+        });
+    }
+    let start = BuilderStart {
+        generics: new_generic_params.clone(),
+        ty: quote! { #builder_name <#(#all_missing_args),*> },
+    };
 
-    Ok(quote! {
+    let code = quote! {
         #[doc=#documentation]
         #(#[doc=#user_doc])*
-        #vis struct #builder_name #all_generic_params #generic_where {
+        #vis struct #builder_name #builder_generic_params #unbounded_where {
             #(#field_defs),*
         }
-        impl #generic_params #builder_name <#(#all_missing_args),*> #generic_where {
-            #vis fn new() -> Self {
+        impl #new_impl_generics #builder_name <#(#all_missing_args),*> #unbounded_where {
+            #vis fn new(#new_params) -> Self {
                 Self {
                     #(#initial_values),*
                 }
             }
         }
-        impl #all_generic_params #builder_name <#(#all_generic_args),*> #generic_where {
+        impl #all_impl_generics #builder_name <#(#all_generic_args),*> #unbounded_where {
             #(#field_mutators)*
         }
-        impl #generic_params #builder_name <#(#all_present_args),*> #generic_where {
-            #vis fn build(self) -> #return_type {
+        impl #impl_generics #builder_name <#(#all_present_args),*> #generic_where {
+            #track_caller
+            #vis #finish_qualifiers fn #finish(self) -> #return_type {
                 #(#constructor_setup)*
                 #constructor_body
             }
         }
-    })
+        #(#option_items)*
+    };
+    Ok((code, start))
 }
 
+/// Returns the name and type of each parameter of a constructor, in order.
 fn make_constructor_args(
     constructor_name: &str,
     param_info: &[ConstructorParam],
     fields: &[FieldInfo],
-) -> Result<TokenStream2, Error> {
+) -> Result<Vec<(Ident, Type)>, Error> {
     let mut param_impls = Vec::new();
     // Stores fields that must be in the parameters of the constructor but the user has not
     // yet explicitly specified where in the parameter list they should go.
     let mut remaining_fields: Vec<_> = fields
         .iter()
-        .cloned()
         .filter(|e| !e.custom_init.contains_key(constructor_name) && e.default_init.is_none())
+        .cloned()
         .collect();
     // If we do not encounter an ellipses, then just insert the extra parameters at the end of the
     // signature.
@@ -587,11 +1278,7 @@ fn make_constructor_args(
                 for (index, field) in remaining_fields.iter().enumerate() {
                     if &field.ident == field_name {
                         let field = remaining_fields.remove(index);
-                        let name = field.ident;
-                        let ty = &field.ty;
-                        param_impls.push(quote! {
-                            #name: #ty
-                        });
+                        param_impls.append(&mut field.params());
                         success = true;
                         break;
                     }
@@ -599,11 +1286,7 @@ fn make_constructor_args(
                 if !success {
                     for field in fields {
                         if &field.ident == field_name {
-                            let name = field.ident.clone();
-                            let ty = &field.ty;
-                            param_impls.push(quote! {
-                                #name: #ty
-                            });
+                            param_impls.push((field.ident.clone(), field.ty.clone()));
                             success = true;
                             break;
                         }
@@ -621,9 +1304,7 @@ fn make_constructor_args(
                 }
             }
             ConstructorParam::Custom(name, ty) => {
-                param_impls.push(quote! {
-                    #name: #ty
-                });
+                param_impls.push((name.clone(), ty.clone()));
             }
             ConstructorParam::Ellipses => {
                 remaining_fields_insertion_index = param_impls.len();
@@ -631,22 +1312,97 @@ fn make_constructor_args(
         }
     }
     for field in remaining_fields {
-        let name = field.ident;
-        let ty = &field.ty;
-        param_impls.insert(
-            remaining_fields_insertion_index,
-            quote! {
-                #name: #ty
-            },
-        );
-        remaining_fields_insertion_index += 1;
+        for param in field.params() {
+            param_impls.insert(remaining_fields_insertion_index, param);
+            remaining_fields_insertion_index += 1;
+        }
     }
+    Ok(param_impls)
+}
+
+/// Like `make_constructor_args`, but also works for constructors which forward to another
+/// constructor, whose parameters are all custom.
+fn constructor_params(
+    info: &ConstructorInfo,
+    fields: &[FieldInfo],
+) -> Result<Vec<(Ident, Type)>, Error> {
+    if info.delegate.is_none() {
+        return make_constructor_args(&info.name.to_string(), &info.params, fields);
+    }
+    Ok(info
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            ConstructorParam::Custom(name, ty) => Some((name.clone(), ty.clone())),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Makes a function which takes the struct apart into a tuple of the values which would be passed
+/// to the constructor to create it again. This is only possible when each field is initialized
+/// directly from a parameter of the same name.
+fn make_constructor_inverse(
+    is_tuple: bool,
+    constructor_name: &Ident,
+    (vis, name): (Visibility, Ident),
+    params: &[(Ident, Type)],
+    fields: &[FieldInfo],
+) -> Result<TokenStream2, Error> {
+    let constructor_str = constructor_name.to_string();
+    for field in fields {
+        let is_param = params.iter().any(|(param, _)| param == &field.ident);
+        if !is_param
+            || field.custom_init.contains_key(&constructor_str)
+            || field.default_init.is_some()
+        {
+            return Err(Error::new_spanned(
+                &name,
+                format!(
+                    "Cannot generate \"{}\" because the field \"{}\" is not a parameter of \"{}\".",
+                    name, field.ident, constructor_name
+                ),
+            ));
+        }
+    }
+    let mut members = Vec::new();
+    let mut types = Vec::new();
+    for (param, _) in params {
+        let index = fields
+            .iter()
+            .position(|field| &field.ident == param)
+            .ok_or_else(|| {
+                Error::new_spanned(
+                    &name,
+                    format!(
+                        "Cannot generate \"{}\" because the parameter \"{}\" of \"{}\" is not a field.",
+                        name, param, constructor_name
+                    ),
+                )
+            })?;
+        members.push(if is_tuple {
+            let index = syn::Index::from(index);
+            quote! { #index }
+        } else {
+            quote! { #param }
+        });
+        types.push(fields[index].ty);
+    }
+    let doc = format!(
+        "Takes this item apart into the values of its fields, in the same order as the parameters of \
+        `{}`.",
+        constructor_name
+    );
     Ok(quote! {
-        #(#param_impls),*
+        #[doc = #doc]
+        #vis fn #name(self) -> (#(#types,)*) {
+            (#(self.#members,)*)
+        }
     })
 }
 
 fn make_constructor_impl(
+    struct_name: &Ident,
     is_tuple: bool,
     info: ConstructorInfo,
     documentation: &[Lit],
@@ -655,40 +1411,107 @@ fn make_constructor_impl(
     let vis = info.vis;
     let name = info.name;
     let name_str = name.to_string();
-    let params = make_constructor_args(&name_str, &info.params[..], fields)?;
+    let generics = info.generics;
+    let generic_where = &generics.where_clause;
     let return_type = info
         .custom_return_type
         .unwrap_or_else(|| parse_quote! { Self });
+    if let Some(Delegate {
+        target,
+        args,
+        question,
+    }) = info.delegate
+    {
+        if info.inverse.is_some() {
+            return Err(Error::new_spanned(
+                name,
+                "Constructors which forward to another constructor cannot have an inverse.",
+            ));
+        }
+        let mut params = Vec::new();
+        for param in info.params {
+            match param {
+                ConstructorParam::Custom(name, ty) => params.push(quote! { #name: #ty }),
+                _ => {
+                    return Err(Error::new_spanned(
+                        name,
+                        "Constructors which forward to another constructor can only have custom \
+                        parameters, like `(size: f32)`.",
+                    ))
+                }
+            }
+        }
+        let call = quote! { Self::#target(#args)#question };
+        let body = match info.return_semantics {
+            ReturnSemantics::Selff => call,
+            ReturnSemantics::Result | ReturnSemantics::Accumulate(_) => {
+                quote! { ::core::result::Result::Ok(#call) }
+            }
+        };
+        return Ok(quote! {
+            #(#[doc = #documentation])*
+            #vis fn #name #generics (#(#params),*) -> #return_type #generic_where {
+                #body
+            }
+        });
+    }
+    let params = make_constructor_args(&name_str, &info.params[..], fields)?;
+    let inverse = match info.inverse {
+        Some(inverse) => make_constructor_inverse(is_tuple, &name, inverse, &params, fields)?,
+        None => quote! {},
+    };
+    let params = params.iter().map(|(name, ty)| quote! { #name: #ty });
+    let mut inits = Vec::new();
     let mut initializers = Vec::new();
     for field in fields {
         let ident = &field.ident;
         let init = field
             .custom_init
             .get(&name_str)
-            .or_else(|| field.default_init.as_ref())
+            .or(field.default_init.as_ref())
             .cloned()
-            .unwrap_or(quote! { #ident });
+            .unwrap_or_else(|| field.param_init());
         let initializer = if is_tuple {
             quote! { #init }
         } else {
             quote! { #ident: #init }
         };
         initializers.push(initializer);
+        inits.push(init);
     }
     let make_self = if is_tuple {
         quote! { Self ( #(#initializers),* ) }
     } else {
         quote! { Self { #(#initializers),* } }
     };
-    let body = match info.return_semantics {
-        ReturnSemantics::Selff => make_self,
-        ReturnSemantics::Result => quote! { ::core::result::Result::Ok(#make_self) },
+    let fallible = !matches!(info.return_semantics, ReturnSemantics::Selff);
+    let mut track_caller = quote! {};
+    let body = if let ReturnSemantics::Accumulate(error_type) = &info.return_semantics {
+        accumulate::make_accumulating(
+            struct_name,
+            fields,
+            is_tuple,
+            inits,
+            quote! { Self },
+            error_type,
+        )
+    } else if check::has_checks(fields) {
+        if !fallible {
+            track_caller = quote! { #[track_caller] };
+        }
+        check::make_checked(struct_name, fields, is_tuple, make_self, fallible)
+    } else if fallible {
+        quote! { ::core::result::Result::Ok(#make_self) }
+    } else {
+        make_self
     };
     Ok(quote! {
         #(#[doc = #documentation])*
-        #vis fn #name (#params) -> #return_type {
+        #track_caller
+        #vis fn #name #generics (#(#params),*) -> #return_type #generic_where {
             #body
         }
+        #inverse
     })
 }
 
@@ -713,6 +1536,74 @@ impl Parse for ValueBody {
     }
 }
 
+/// Reads the `#[value()]` attributes of a field. `item_names` lists the builders and constructors
+/// which `#[value(.. for ..)]` is allowed to refer to.
+fn make_field_info<'a>(
+    ident: Ident,
+    ty: &'a Type,
+    attrs: &[Attribute],
+    item_names: &HashSet<String>,
+) -> Result<FieldInfo<'a>, Error> {
+    let mut custom_init = HashMap::new();
+    let mut default_init = None;
+    let mut flatten = None;
+    let mut checks = Vec::new();
+    let mut env = None;
+    let mut doc = Vec::new();
+    for attr in attrs {
+        if attr.path.is_ident("doc") {
+            if let Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(line),
+                ..
+            })) = attr.parse_meta()
+            {
+                doc.push(line.value().trim().to_owned());
+            }
+        } else if attr.path.is_ident("flatten") {
+            flatten = Some(syn::parse2(attr.tokens.clone())?);
+        } else if attr.path.is_ident("env") {
+            env = Some(env::parse_env.parse2(attr.tokens.clone())?);
+        } else if attr.path.is_ident("check") {
+            checks.append(&mut check::parse_checks.parse2(attr.tokens.clone())?);
+        } else if attr.path.is_ident("value") {
+            let vb: ValueBody = syn::parse2(attr.tokens.clone())?;
+            let expr = vb.expr;
+            let initializer = quote! { #expr };
+            if let Some(for_item) = vb.for_item {
+                let item_name = for_item.to_string();
+                if !item_names.contains(&item_name) {
+                    return Err(Error::new_spanned(
+                        for_item,
+                        format!(
+                            "The identifier \"{}\" does not refer to a constructor or builder.",
+                            item_name
+                        ),
+                    ));
+                }
+                custom_init.insert(item_name, initializer);
+            } else {
+                default_init = Some(initializer);
+            }
+        }
+    }
+    if let (Some(env), Some(_)) = (&env, &flatten) {
+        return Err(Error::new_spanned(
+            env,
+            "Flattened fields cannot be read from environment variables.",
+        ));
+    }
+    Ok(FieldInfo {
+        ident,
+        ty,
+        custom_init,
+        default_init,
+        flatten,
+        checks,
+        env,
+        doc,
+    })
+}
+
 fn path_equal(p1: &Path, p2: &Path) -> bool {
     #[allow(clippy::if_same_then_else)]
     if p1.leading_colon.is_some() != p2.leading_colon.is_some() {
@@ -739,7 +1630,8 @@ fn make_generic_args(params: &Generics) -> Vec<TokenStream2> {
                 args.push(quote! { #ident });
             }
             GenericParam::Lifetime(lt) => {
-                args.push(quote! { #lt });
+                let lifetime = &lt.lifetime;
+                args.push(quote! { #lifetime });
             }
             GenericParam::Const(cp) => {
                 let ident = &cp.ident;
@@ -750,6 +1642,28 @@ fn make_generic_args(params: &Generics) -> Vec<TokenStream2> {
     args
 }
 
+/// Builders may not use every generic parameter of the struct they build (for example when the
+/// only field using a parameter has a `#[value()]` attribute), so they carry a marker which uses
+/// all of them without affecting size, variance or auto traits.
+fn make_generics_marker(params: &Generics) -> TokenStream2 {
+    let mut used = Vec::new();
+    for param in params.params.iter() {
+        match param {
+            GenericParam::Type(tp) => {
+                let ident = &tp.ident;
+                used.push(quote! { fn() -> ::core::marker::PhantomData<#ident> });
+            }
+            GenericParam::Lifetime(lt) => {
+                let lifetime = &lt.lifetime;
+                used.push(quote! { fn() -> &#lifetime () });
+            }
+            // Const parameters do not need to be used.
+            GenericParam::Const(..) => (),
+        }
+    }
+    quote! { ::core::marker::PhantomData<(#(#used,)*)> }
+}
+
 struct GenerateItemsContent {
     args: TokenStream2,
 }
@@ -777,63 +1691,136 @@ impl Parse for MaybeDocComment {
     }
 }
 
-fn make_item<ItemType: Parse>(
-    input_attr: TokenStream,
-    item: TokenStream,
-    label: &str,
-) -> TokenStream {
-    let input_attr2: TokenStream2 = input_attr.clone().into();
-    // Check that the input is valid.
-    let _: ItemType = syn::parse_macro_input!(input_attr);
-    let label = format_ident!("{}", label);
-    let macro_arg = quote! { #label { #input_attr2 } };
+/// The attributes which are collected into a single `#[generate_items__]`, along with the label
+/// used for them in its arguments.
+const ITEM_ATTRIBUTES: &[(&str, &str)] = &[
+    ("make_constructor", "constructor"),
+    ("make_builder", "builder"),
+    ("make_accessors", "accessors"),
+    ("make_newtype", "newtype"),
+    ("make_factory", "factory"),
+    ("make_describe", "describe"),
+];
+
+/// Recognizes `#[make_x]`, `#[scones::make_x]` and `#[::scones::make_x]`.
+fn item_attribute_label(path: &Path) -> Option<&'static str> {
+    let segments: Vec<_> = path.segments.iter().collect();
+    let name = match segments[..] {
+        [name] if path.leading_colon.is_none() => name,
+        [krate, name] if krate.ident == "scones" => name,
+        _ => return None,
+    };
+    ITEM_ATTRIBUTES
+        .iter()
+        .find(|(attr, _)| name.ident == attr)
+        .map(|(_, label)| *label)
+}
+
+fn check_item_args(label: &str, args: TokenStream2) -> syn::parse::Result<()> {
+    match label {
+        "constructor" => syn::parse2::<ConstructorInfo>(args).map(|_| ()),
+        "builder" => syn::parse2::<PartialBuilderInfo>(args).map(|_| ()),
+        "accessors" => syn::parse2::<AccessorsInfo>(args).map(|_| ()),
+        "newtype" => syn::parse2::<NewtypeInfo>(args).map(|_| ()),
+        "describe" if args.is_empty() => Ok(()),
+        "describe" => Err(Error::new_spanned(
+            args,
+            "make_describe does not take any arguments.",
+        )),
+        #[cfg(feature = "test-factories")]
+        "factory" => syn::parse2::<FactoryInfo>(args).map(|_| ()),
+        #[cfg(not(feature = "test-factories"))]
+        "factory" => Err(Error::new_spanned(
+            args,
+            "make_factory requires the test-factories feature of scones to be enabled.",
+        )),
+        _ => unreachable!("Unknown item label"),
+    }
+}
+
+/// Arguments given to scones attributes which have already been taken care of by another scones
+/// attribute on the same item. They are kept on the item so that their paths are still resolved.
+fn handled_marker() -> TokenStream2 {
+    quote! { (@handled) }
+}
+
+fn is_handled(input_attr: &TokenStream2) -> bool {
+    quote! { (#input_attr) }.to_string() == handled_marker().to_string()
+}
+
+fn make_item(input_attr: TokenStream, item: TokenStream, label: &str) -> TokenStream {
+    let input_attr: TokenStream2 = input_attr.into();
+    if is_handled(&input_attr) {
+        return item;
+    }
     let mut struct_def: ItemStruct = syn::parse_macro_input!(item);
-    let mut found = false;
-    let mut consume_doc = true;
-    let mut user_documentation = Vec::new();
-    let mut condemned_indexes = Vec::new();
-    for (index, attr) in struct_def.attrs.iter_mut().enumerate() {
+    // Whichever scones attribute is expanded first takes care of all the others on the item as
+    // well, so it does not matter where they are placed relative to other attributes. Each
+    // `/// ^` comment belongs to the closest scones attribute before it. Comments before all of
+    // the remaining scones attributes belong to the one being expanded, since the compiler has
+    // already removed it from the item.
+    let mut invocations = vec![(label, input_attr, Vec::new())];
+    let mut existing_index = None;
+    let mut kept_attrs = Vec::new();
+    for mut attr in struct_def.attrs.drain(..) {
+        if let Some(label) = item_attribute_label(&attr.path) {
+            let args = if attr.tokens.is_empty() {
+                quote! {}
+            } else {
+                match syn::parse2::<GenerateItemsContent>(attr.tokens) {
+                    Ok(content) => content.args,
+                    Err(err) => return err.to_compile_error().into(),
+                }
+            };
+            invocations.push((label, args, Vec::new()));
+            attr.tokens = handled_marker();
+            kept_attrs.push(attr);
+            continue;
+        }
         if path_equal(&attr.path, &parse_quote! { doc }) {
-            if let MaybeDocComment(Some(content)) = syn::parse2(attr.tokens.clone()).unwrap() {
-                if consume_doc && content.starts_with(" ^") {
-                    user_documentation.push(String::from(&content[2..]));
-                    condemned_indexes.push(index);
+            if let Ok(MaybeDocComment(Some(content))) = syn::parse2(attr.tokens.clone()) {
+                if let Some(content) = content.strip_prefix(" ^") {
+                    let (_, _, documentation) = invocations.last_mut().unwrap();
+                    documentation.push(String::from(content));
+                    continue;
                 }
             }
-        } else {
-            consume_doc = false;
         }
-        // This will be the last attribute so we don't have to worry about finding it before we
-        // consume all the relevant doc comments.
-        if path_equal(&attr.path, &parse_quote! { ::scones::generate_items__}) {
-            let old_args: GenerateItemsContent = syn::parse2(attr.tokens.clone()).unwrap();
-            let old_args = old_args.args;
-            attr.tokens = quote! { ( #old_args #macro_arg { #(#user_documentation),* } ) };
-            found = true;
-            break;
+        if path_equal(&attr.path, &parse_quote! { ::scones::generate_items__ }) {
+            existing_index = Some(kept_attrs.len());
         }
+        kept_attrs.push(attr);
     }
-    if !found {
+    let mut macro_args = Vec::new();
+    for (label, args, documentation) in invocations {
+        // Check that the input is valid.
+        if let Err(err) = check_item_args(label, args.clone()) {
+            return err.to_compile_error().into();
+        }
+        let label = format_ident!("{}", label);
+        macro_args.push(quote! { #label { #args } { #(#documentation),* } });
+    }
+    if let Some(index) = existing_index {
+        let attr = &mut kept_attrs[index];
+        let old_args: GenerateItemsContent = syn::parse2(attr.tokens.clone()).unwrap();
+        let old_args = old_args.args;
+        attr.tokens = quote! { ( #old_args #(#macro_args)* ) };
+    } else {
         let attr_def = quote! {
-            #[::scones::generate_items__(#macro_arg { #(#user_documentation),* } )]
+            #[::scones::generate_items__( #(#macro_args)* )]
         };
-        let mut insert_at = 0;
-        // Make sure we don't insert before #[derive()].
-        for (index, attr) in struct_def.attrs.iter().enumerate() {
-            if path_equal(&attr.path, &parse_quote! { derive }) {
-                break;
-            }
-            insert_at = index + 1;
-        }
-        struct_def.attrs.insert(
+        // Make sure we don't insert after #[derive()], so that #[value()] attributes are removed
+        // before any derive macros see them.
+        let insert_at = kept_attrs
+            .iter()
+            .position(|attr| path_equal(&attr.path, &parse_quote! { derive }))
+            .unwrap_or(kept_attrs.len());
+        kept_attrs.insert(
             insert_at,
             (Attribute::parse_outer).parse2(attr_def).unwrap().remove(0),
         );
     }
-    condemned_indexes.reverse();
-    for index in condemned_indexes {
-        struct_def.attrs.remove(index);
-    }
+    struct_def.attrs = kept_attrs;
     (quote! { #struct_def }).into()
 }
 
@@ -841,19 +1828,63 @@ fn make_item<ItemType: Parse>(
 // invocation.
 #[proc_macro_attribute]
 pub fn make_constructor(input_attr: TokenStream, item: TokenStream) -> TokenStream {
-    make_item::<ConstructorInfo>(input_attr, item, "constructor")
+    make_item(input_attr, item, "constructor")
 }
 
 // This can be invoked multiple times and it will produce a single #[generate_items__]
 // invocation.
 #[proc_macro_attribute]
 pub fn make_builder(input_attr: TokenStream, item: TokenStream) -> TokenStream {
-    make_item::<PartialBuilderInfo>(input_attr, item, "builder")
+    let attr: TokenStream2 = input_attr.clone().into();
+    if is_handled(&attr) {
+        return item;
+    }
+    let tokens: TokenStream2 = item.clone().into();
+    let result = if let Ok(item_fn) = syn::parse2::<ItemFn>(tokens.clone()) {
+        function::make_fn_builders(attr, item_fn)
+    } else if let Ok(item_impl) = syn::parse2::<ItemImpl>(tokens) {
+        function::make_impl_builders(attr, item_impl)
+    } else {
+        return make_item(input_attr, item, "builder");
+    };
+    result.unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+// This can be invoked multiple times and it will produce a single #[generate_items__]
+// invocation.
+#[proc_macro_attribute]
+pub fn make_accessors(input_attr: TokenStream, item: TokenStream) -> TokenStream {
+    make_item(input_attr, item, "accessors")
+}
+
+// This is combined into a single #[generate_items__] invocation along with the other attributes.
+#[proc_macro_attribute]
+pub fn make_newtype(input_attr: TokenStream, item: TokenStream) -> TokenStream {
+    make_item(input_attr, item, "newtype")
+}
+
+// This is combined into a single #[generate_items__] invocation along with the other attributes.
+#[proc_macro_attribute]
+pub fn make_describe(input_attr: TokenStream, item: TokenStream) -> TokenStream {
+    make_item(input_attr, item, "describe")
+}
+
+// This is combined into a single #[generate_items__] invocation along with the other attributes.
+#[cfg(feature = "test-factories")]
+#[proc_macro_attribute]
+pub fn make_factory(input_attr: TokenStream, item: TokenStream) -> TokenStream {
+    make_item(input_attr, item, "factory")
 }
 
 struct GenerateItemsArgs {
     builders: Vec<(PartialBuilderInfo, Vec<Lit>)>,
     constructors: Vec<(ConstructorInfo, Vec<Lit>)>,
+    accessors: Vec<AccessorsInfo>,
+    newtypes: Vec<NewtypeInfo>,
+    #[cfg(feature = "test-factories")]
+    factories: Vec<FactoryInfo>,
+    /// Whether `scones::Describe` should be implemented.
+    describe: bool,
 }
 
 impl Parse for GenerateItemsArgs {
@@ -861,6 +1892,11 @@ impl Parse for GenerateItemsArgs {
         let mut result = Self {
             builders: Vec::new(),
             constructors: Vec::new(),
+            accessors: Vec::new(),
+            newtypes: Vec::new(),
+            #[cfg(feature = "test-factories")]
+            factories: Vec::new(),
+            describe: false,
         };
         while !input.is_empty() {
             let kind: Ident = input.parse()?;
@@ -875,6 +1911,37 @@ impl Parse for GenerateItemsArgs {
                 result.constructors.push((content.parse()?, documentation));
             } else if kind == "builder" {
                 result.builders.push((content.parse()?, documentation));
+            } else if kind == "accessors"
+                || kind == "newtype"
+                || kind == "factory"
+                || kind == "describe"
+            {
+                if let Some(doc) = documentation.first() {
+                    return Err(Error::new_spanned(
+                        doc,
+                        format!(
+                            "Items generated by make_{} are documented automatically, so they \
+                            cannot be given `/// ^` comments.",
+                            kind
+                        ),
+                    ));
+                }
+                if kind == "accessors" {
+                    result.accessors.push(content.parse()?);
+                } else if kind == "newtype" {
+                    result.newtypes.push(content.parse()?);
+                } else if kind == "describe" {
+                    if result.describe {
+                        return Err(Error::new(
+                            kind.span(),
+                            "make_describe can only be used once on each struct.",
+                        ));
+                    }
+                    result.describe = true;
+                } else {
+                    #[cfg(feature = "test-factories")]
+                    result.factories.push(content.parse()?);
+                }
             } else {
                 unreachable!("Bad syntax generation");
             }
@@ -883,19 +1950,63 @@ impl Parse for GenerateItemsArgs {
     }
 }
 
-/// This is the actual macro that generates constructors. Use #{make_constructor} to invoke it.
-#[doc(hidden)]
-#[proc_macro_attribute]
-pub fn generate_items__(attr: TokenStream, item: TokenStream) -> TokenStream {
+fn struct_fields(struct_def: &ItemStruct) -> Result<(&Punctuated<Field, Comma>, bool), Error> {
+    match &struct_def.fields {
+        Fields::Named(fields) => Ok((&fields.named, false)),
+        Fields::Unnamed(fields) => Ok((&fields.unnamed, true)),
+        Fields::Unit => Err(Error::new_spanned(
+            struct_def,
+            "Cannot use make_constructor or make_builder on a unit struct.",
+        )),
+    }
+}
+
+fn make_field_infos<'a>(
+    fields: &'a Punctuated<Field, Comma>,
+    item_names: &HashSet<String>,
+) -> Result<Vec<FieldInfo<'a>>, Error> {
+    let mut field_infos = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let ident = field
+            .ident
+            .clone()
+            .unwrap_or_else(|| format_ident!("field_{}", index));
+        field_infos.push(make_field_info(ident, &field.ty, &field.attrs, item_names)?);
+    }
+    Ok(field_infos)
+}
+
+/// Which macro `generate_items()` is called from.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ItemsSource {
+    /// One of the item attributes, like `#[make_constructor]`.
+    Attribute,
+    /// `#[derive(Constructor)]`.
+    ConstructorDerive,
+    /// `#[derive(Builder)]`.
+    BuilderDerive,
+}
+
+/// Generates the builders and constructors described by `args` for `struct_def`. Other items which
+/// `#[value(.. for ..)]` attributes are allowed to refer to can be listed in `other_item_names`.
+fn generate_items(
+    args: GenerateItemsArgs,
+    mut item_names: HashSet<String>,
+    struct_def: &ItemStruct,
+    source: ItemsSource,
+) -> Result<TokenStream2, Error> {
     let GenerateItemsArgs {
         builders,
-        constructors,
-    } = syn::parse_macro_input!(attr);
-    let mut item_names: HashSet<String> = HashSet::new();
+        mut constructors,
+        accessors,
+        mut newtypes,
+        #[cfg(feature = "test-factories")]
+        factories,
+        describe,
+    } = args;
     for (c, _) in &constructors {
         item_names.insert(c.name.to_string());
     }
-    let mut struct_def: ItemStruct = syn::parse_macro_input!(item);
     let generic_params = &struct_def.generics;
     let struct_name = &struct_def.ident;
     let builders: Vec<_> = builders
@@ -905,97 +2016,328 @@ pub fn generate_items__(attr: TokenStream, item: TokenStream) -> TokenStream {
     for b in &builders {
         item_names.insert(b.name.to_string());
     }
-
-    let (fields, is_tuple) = if let Fields::Named(fields) = &mut struct_def.fields {
-        (&mut fields.named, false)
-    } else if let Fields::Unnamed(fields) = &mut struct_def.fields {
-        (&mut fields.unnamed, true)
-    } else {
-        return Error::new_spanned(
-            &struct_def,
-            "Cannot use make_constructor or make_builder on a unit struct.",
-        )
-        .to_compile_error()
-        .into();
-    };
-    let mut field_infos = Vec::new();
-    for (index, field) in fields.into_iter().enumerate() {
-        let ident = field
-            .ident
-            .clone()
-            .unwrap_or_else(|| format_ident!("field_{}", index));
-        let mut condemned_indexes = Vec::new();
-        let mut custom_init = HashMap::new();
-        let mut default_init = None;
-        for (index, attr) in field.attrs.iter().enumerate() {
-            if attr.path.is_ident("value") {
-                condemned_indexes.push(index);
-                let tokens = attr.tokens.clone().into();
-                let vb: ValueBody = syn::parse_macro_input!(tokens);
-                let expr = vb.expr;
-                let initializer = quote! { #expr };
-                if let Some(for_item) = vb.for_item {
-                    let item_name = for_item.to_string();
-                    if !item_names.contains(&item_name) {
-                        return Error::new_spanned(
-                            for_item,
-                            format!(
-                                "The identifier \"{}\" does not refer to a constructor or builder.",
-                                item_name
-                            ),
-                        )
-                        .to_compile_error()
-                        .into();
-                    }
-                    custom_init.insert(item_name, initializer);
-                } else {
-                    default_init = Some(initializer);
-                }
-            }
+    // Every option other than untyped implements a trait or generates items whose names only
+    // depend on the struct.
+    for unique_option in CONSTRUCTOR_OPTIONS {
+        if let Some(second) = constructors
+            .iter()
+            .filter_map(|(c, _)| c.options.iter().find(|option| option == unique_option))
+            .nth(1)
+        {
+            return Err(Error::new_spanned(
+                second,
+                format!(
+                    "Only one constructor of each struct can use the {} option.",
+                    unique_option
+                ),
+            ));
         }
-        condemned_indexes.reverse();
-        for index in condemned_indexes {
-            field.attrs.remove(index);
+    }
+    for unique_option in BUILDER_OPTIONS
+        .iter()
+        .filter(|option| **option != "untyped")
+    {
+        if let Some(second) = builders
+            .iter()
+            .filter_map(|b| b.options.iter().find(|option| option == unique_option))
+            .nth(1)
+        {
+            return Err(Error::new_spanned(
+                second,
+                format!(
+                    "Only one builder of each struct can use the {} option.",
+                    unique_option
+                ),
+            ));
         }
-        field_infos.push(FieldInfo {
-            ident,
-            ty: &field.ty,
-            custom_init,
-            default_init,
-        });
     }
 
+    let (fields, is_tuple) = struct_fields(struct_def)?;
+    let mut field_infos = make_field_infos(fields, &item_names)?;
+    if newtypes.len() > 1 {
+        return Err(Error::new_spanned(
+            struct_name,
+            "make_newtype can only be used once on each struct.",
+        ));
+    }
+    let newtype = newtypes.pop();
+    if let Some(info) = &newtype {
+        let (constructor, doc, init) = newtype::make_newtype_constructor(info, struct_def)?;
+        field_infos[0]
+            .custom_init
+            .insert(constructor.name.to_string(), init);
+        constructors.push((constructor, doc));
+    }
+    let describe_code = if describe {
+        describe::make_describe_impl(
+            struct_name,
+            generic_params,
+            &field_infos,
+            &constructors.iter().map(|(c, _)| c).collect::<Vec<_>>(),
+            &builders.iter().collect::<Vec<_>>(),
+        )?
+    } else {
+        quote! {}
+    };
+
+    #[cfg(feature = "test-factories")]
+    let factory_code = factories
+        .into_iter()
+        .map(|info| factory::make_factory_impl(info, struct_def, &builders, &field_infos))
+        .collect::<Result<Vec<_>, _>>()?;
+    #[cfg(not(feature = "test-factories"))]
+    let factory_code: Vec<TokenStream2> = Vec::new();
+
     let mut builder_code = Vec::new();
+    let target = BuilderTarget::Struct {
+        name: struct_name.clone(),
+        is_tuple,
+    };
+    let mut env_params = HashSet::new();
     for builder in builders {
-        match make_builder_impl(
-            &struct_name,
-            is_tuple,
-            &generic_params,
+        let (code, _) = make_builder_impl(
+            &target,
+            generic_params,
             builder,
             &field_infos[..],
-        ) {
-            Ok(def) => builder_code.push(def),
-            Err(err) => return err.to_compile_error().into(),
-        }
+            &mut env_params,
+        )?;
+        builder_code.push(code);
+    }
+    // Deriving Constructor never generates builders, so the variables may be read by a derived
+    // Builder instead.
+    if source != ItemsSource::ConstructorDerive {
+        env::check_env_read(&field_infos, &env_params)?;
     }
+    // `arbitrary` is the only constructor option, so without its feature any option is an error.
+    #[cfg(feature = "arbitrary")]
+    let option_items = constructors
+        .iter()
+        .filter(|(cons, _)| cons.options.iter().any(|option| option == "arbitrary"))
+        .map(|(cons, _)| {
+            arbitrary::make_arbitrary_impl(struct_name, generic_params, cons, &field_infos)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    #[cfg(not(feature = "arbitrary"))]
+    let option_items: Vec<TokenStream2> = match constructors
+        .iter()
+        .flat_map(|(cons, _)| &cons.options)
+        .next()
+    {
+        Some(option) => {
+            return Err(Error::new_spanned(
+                option,
+                format!(
+                    "The {} option requires the {} feature of scones to be enabled.",
+                    option, option
+                ),
+            ))
+        }
+        None => Vec::new(),
+    };
     let mut constructor_defs = Vec::new();
     for (cons, doc) in constructors {
-        match make_constructor_impl(is_tuple, cons, &doc[..], &field_infos[..]) {
-            Ok(def) => constructor_defs.push(def),
-            Err(err) => return err.to_compile_error().into(),
-        }
+        constructor_defs.push(make_constructor_impl(
+            struct_name,
+            is_tuple,
+            cons,
+            &doc[..],
+            &field_infos[..],
+        )?);
     }
 
-    let generic_param_list = &generic_params.params;
-    let generic_where = &generic_params.where_clause;
-    let generic_args = make_generic_args(&generic_params);
+    let accessor_defs = accessors::make_accessors_impl(&accessors, fields)?;
+    let check_error = check::make_check_error(&struct_def.vis, struct_name, &field_infos);
+    let newtype_code = match newtype {
+        Some(info) => newtype::make_newtype_impl(info, struct_def)?,
+        None => quote! {},
+    };
 
-    (quote! {
-        #struct_def
+    let (impl_generics, type_generics, generic_where) = generic_params.split_for_impl();
+
+    Ok(quote! {
         #(#builder_code)*
-        impl <#generic_param_list> #struct_name <#(#generic_args),*> #generic_where {
+        impl #impl_generics #struct_name #type_generics #generic_where {
             #(#constructor_defs)*
+            #(#accessor_defs)*
         }
+        #newtype_code
+        #check_error
+        #(#option_items)*
+        #describe_code
+        #(#factory_code)*
+    })
+}
+
+/// This is the actual macro that generates constructors. Use #{make_constructor} to invoke it.
+#[doc(hidden)]
+#[proc_macro_attribute]
+pub fn generate_items__(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args: GenerateItemsArgs = syn::parse_macro_input!(attr);
+    let mut struct_def: ItemStruct = syn::parse_macro_input!(item);
+    let items = match generate_items(args, HashSet::new(), &struct_def, ItemsSource::Attribute) {
+        Ok(items) => items,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    // #[value()], #[flatten()], #[check()], #[env()], #[accessors()] and #[factory()] are not real
+    // attributes, so they have to be removed once we are done with them.
+    for field in struct_def.fields.iter_mut() {
+        field.attrs.retain(|attr| {
+            !attr.path.is_ident("value")
+                && !attr.path.is_ident("flatten")
+                && !attr.path.is_ident("check")
+                && !attr.path.is_ident("env")
+                && !attr.path.is_ident("accessors")
+                && !attr.path.is_ident("factory")
+        });
+    }
+
+    (quote! {
+        #struct_def
+        #items
     })
     .into()
 }
+
+/// Implements `scones::Describe` for the constructors and builders described by `args`, without
+/// generating them.
+fn describe_items(args: GenerateItemsArgs, struct_def: &ItemStruct) -> Result<TokenStream2, Error> {
+    let struct_name = &struct_def.ident;
+    let builders: Vec<_> = args
+        .builders
+        .into_iter()
+        .map(|(b, doc)| b.complete(struct_name, doc))
+        .collect();
+    let constructors: Vec<_> = args.constructors.iter().map(|(c, _)| c).collect();
+    let item_names = constructors
+        .iter()
+        .map(|c| c.name.to_string())
+        .chain(builders.iter().map(|b| b.name.to_string()))
+        .collect();
+    let (fields, _) = struct_fields(struct_def)?;
+    let field_infos = make_field_infos(fields, &item_names)?;
+    describe::make_describe_impl(
+        struct_name,
+        &struct_def.generics,
+        &field_infos,
+        &constructors,
+        &builders.iter().collect::<Vec<_>>(),
+    )
+}
+
+/// Shared implementation of `#[derive(Constructor)]`, `#[derive(Builder)]` and `#[derive(Describe)]`.
+/// All of them read all of the `#[constructor()]` and `#[builder()]` helper attributes so that
+/// `#[value(.. for ..)]` can refer to any of them, but the first two only generate the items of
+/// their own kind.
+fn derive_items(item: TokenStream, label: &str) -> TokenStream {
+    let struct_def: ItemStruct = syn::parse_macro_input!(item);
+    let mut args = GenerateItemsArgs {
+        builders: Vec::new(),
+        constructors: Vec::new(),
+        accessors: Vec::new(),
+        newtypes: Vec::new(),
+        #[cfg(feature = "test-factories")]
+        factories: Vec::new(),
+        describe: false,
+    };
+    let mut other_item_names = HashSet::new();
+    let mut found = false;
+    for attr in &struct_def.attrs {
+        let attr_label = if attr.path.is_ident("constructor") {
+            "constructor"
+        } else if attr.path.is_ident("builder") {
+            "builder"
+        } else {
+            continue;
+        };
+        let tokens = if attr.tokens.is_empty() {
+            quote! {}
+        } else {
+            match syn::parse2::<GenerateItemsContent>(attr.tokens.clone()) {
+                Ok(content) => content.args,
+                Err(err) => return err.to_compile_error().into(),
+            }
+        };
+        let result = if attr_label == "constructor" {
+            syn::parse2::<ConstructorInfo>(tokens).map(|info| {
+                if label == attr_label || label == "describe" {
+                    args.constructors.push((info, Vec::new()));
+                } else {
+                    other_item_names.insert(info.name.to_string());
+                }
+            })
+        } else {
+            syn::parse2::<PartialBuilderInfo>(tokens).map(|info| {
+                if label == attr_label || label == "describe" {
+                    args.builders.push((info, Vec::new()));
+                } else {
+                    other_item_names.insert(info.final_name(&struct_def.ident).to_string());
+                }
+            })
+        };
+        if let Err(err) = result {
+            return err.to_compile_error().into();
+        }
+        found |= label == attr_label;
+    }
+    if label == "describe" {
+        return describe_items(args, &struct_def)
+            .unwrap_or_else(|err| err.to_compile_error())
+            .into();
+    }
+    // Both derives can be used on the same struct without seeing each other, so neither of them
+    // can define the check error.
+    if let Some(attr) = struct_def
+        .fields
+        .iter()
+        .flat_map(|field| &field.attrs)
+        .find(|attr| attr.path.is_ident("check"))
+    {
+        return Error::new_spanned(
+            attr,
+            "#[check()] can only be used with #[make_constructor] and #[make_builder].",
+        )
+        .to_compile_error()
+        .into();
+    }
+    // Deriving without any helper attributes is the same as using the attribute macro without
+    // any arguments.
+    if !found {
+        let result = if label == "constructor" {
+            syn::parse2(quote! {}).map(|info| args.constructors.push((info, Vec::new())))
+        } else {
+            syn::parse2(quote! {}).map(|info| args.builders.push((info, Vec::new())))
+        };
+        if let Err(err) = result {
+            return err.to_compile_error().into();
+        }
+    }
+    let source = if label == "constructor" {
+        ItemsSource::ConstructorDerive
+    } else {
+        ItemsSource::BuilderDerive
+    };
+    match generate_items(args, other_item_names, &struct_def, source) {
+        Ok(items) => items.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive-style equivalent of #{make_constructor}, configured with `#[constructor()]` attributes.
+#[proc_macro_derive(Constructor, attributes(constructor, value, flatten, check, env))]
+pub fn derive_constructor(item: TokenStream) -> TokenStream {
+    derive_items(item, "constructor")
+}
+
+/// Derive-style equivalent of #{make_builder}, configured with `#[builder()]` attributes.
+#[proc_macro_derive(Builder, attributes(builder, value, flatten, check, env))]
+pub fn derive_builder(item: TokenStream) -> TokenStream {
+    derive_items(item, "builder")
+}
+
+/// Implements `scones::Describe` for the constructors and builders given by `#[constructor()]` and
+/// `#[builder()]` attributes.
+#[proc_macro_derive(Describe, attributes(constructor, builder, value, flatten, check, env))]
+pub fn derive_describe(item: TokenStream) -> TokenStream {
+    derive_items(item, "describe")
+}
@@ -0,0 +1,169 @@
+//! Builders which take several values for each parameter and build every combination of them, for
+//! use in combinatorial tests.
+
+use crate::{
+    make_generic_args, make_generics_marker, partial, unique_ident, BuilderField, RuntimeBuilder,
+};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::Ident;
+
+/// Generates `<StructName>Matrix`, which holds a list of values for each parameter of the builder
+/// and builds an instance for every combination of them.
+pub(crate) fn make_matrix(builder: &RuntimeBuilder) -> TokenStream2 {
+    let RuntimeBuilder {
+        vis,
+        struct_name,
+        generics,
+        builder_name,
+        ..
+    } = builder;
+    let matrix_name = format_ident!("{}Matrix", struct_name);
+    let value = Ident::new("value", Span::mixed_site());
+    let values = Ident::new("values", Span::mixed_site());
+    let values_param = Ident::new("Values__", Span::mixed_site());
+    let index = Ident::new("index", Span::mixed_site());
+    let rest = Ident::new("rest", Span::mixed_site());
+    let total = Ident::new("total", Span::mixed_site());
+    let missing = Ident::new("missing", Span::mixed_site());
+    let mut taken_fields = builder
+        .params
+        .iter()
+        .map(|field| field.borrow_name().to_string())
+        .collect();
+    let marker_field = unique_ident("generics_marker__", &mut taken_fields);
+    let marker = make_generics_marker(generics);
+    let mut field_defs = Vec::new();
+    let mut initial_values = vec![quote! { #marker_field: ::core::marker::PhantomData }];
+    let mut setters = Vec::new();
+    let mut missing_checks = Vec::new();
+    let mut counts = Vec::new();
+    let mut clone_bounds = Vec::new();
+    for field in builder.params {
+        let name = field.borrow_name();
+        let ty = field.borrow_type();
+        let setter = &builder.setter_names[&name.to_string()];
+        let each_setter = format_ident!("{}_each", setter);
+        field_defs.push(quote! { #name: ::core::option::Option<::std::vec::Vec<#ty>> });
+        initial_values.push(quote! { #name: ::core::option::Option::None });
+        let doc = format!("Makes every item use `value` for `{}`.", name);
+        let each_doc = format!(
+            "Builds items with each of `values` for `{}`. Every combination of the values given \
+            to the other parameters is built once for each of them.",
+            name
+        );
+        setters.push(quote! {
+            #[doc = #doc]
+            #vis fn #setter(mut self, #value: #ty) -> Self {
+                self.#name = ::core::option::Option::Some(::std::vec![#value]);
+                self
+            }
+
+            #[doc = #each_doc]
+            #vis fn #each_setter<#values_param>(mut self, #values: #values_param) -> Self
+            where
+                #values_param: ::core::iter::IntoIterator<Item = #ty>,
+            {
+                self.#name = ::core::option::Option::Some(
+                    ::core::iter::Iterator::collect(::core::iter::IntoIterator::into_iter(#values)),
+                );
+                self
+            }
+        });
+        if let BuilderField::Required { .. } = field {
+            let name_str = name.to_string();
+            missing_checks.push(quote! {
+                if self.#name.is_none() {
+                    #missing.push(#name_str);
+                }
+            });
+        }
+        counts.push(quote! {
+            self.#name.as_ref().map_or(1, ::std::vec::Vec::len)
+        });
+        clone_bounds.push(quote! { #ty: ::core::clone::Clone });
+    }
+    // The last parameter changes fastest, like the innermost of a set of nested loops.
+    let picks = builder.params.iter().rev().map(|field| {
+        let name = field.borrow_name();
+        quote! {
+            let #name = match &self.#name {
+                ::core::option::Option::Some(#values) => {
+                    let #value = ::core::clone::Clone::clone(&#values[#rest % #values.len()]);
+                    #rest /= #values.len();
+                    ::core::option::Option::Some(#value)
+                }
+                ::core::option::Option::None => ::core::option::Option::None,
+            };
+        }
+    });
+    let build = partial::make_runtime_build(builder, |name| quote! { #name });
+    let generic_args = make_generic_args(generics);
+    let item = quote! { #struct_name <#(#generic_args),*> };
+    let item = match builder.error_type {
+        Some(error_type) => quote! { ::core::result::Result<#item, #error_type> },
+        None => item,
+    };
+    let (impl_generics, type_generics, generic_where) = generics.split_for_impl();
+    let doc = format!(
+        "Holds a list of values for each parameter of `{}`, and builds an instance of `{}` for \
+        every combination of them.",
+        builder_name, struct_name,
+    );
+    quote! {
+        #[doc = #doc]
+        #vis struct #matrix_name #generics #generic_where {
+            #(#field_defs,)*
+            #marker_field: #marker,
+        }
+
+        impl #impl_generics #matrix_name #type_generics #generic_where {
+            /// Creates a matrix where nothing has been set. Optional parameters which are never
+            /// set use their default value.
+            #vis fn new() -> Self {
+                Self {
+                    #(#initial_values),*
+                }
+            }
+
+            #(#setters)*
+
+            /// Returns an iterator which builds an item for every combination of the values which
+            /// were given, or an error listing the required values which were never set.
+            #vis fn build_all(
+                self,
+            ) -> ::core::result::Result<
+                impl ::core::iter::Iterator<Item = #item>,
+                ::scones::BuildError,
+            >
+            where
+                #(#clone_bounds,)*
+            {
+                #[allow(unused_mut)]
+                let mut #missing: ::std::vec::Vec<&'static str> = ::std::vec::Vec::new();
+                #(#missing_checks)*
+                if !#missing.is_empty() {
+                    return ::core::result::Result::Err(::scones::BuildError::Missing(#missing));
+                }
+                let #total: usize = 1 #(* #counts)*;
+                ::core::result::Result::Ok((0..#total).map(move |#index| {
+                    #[allow(unused_mut, unused_variables)]
+                    let mut #rest = #index;
+                    #(#picks)*
+                    match #build {
+                        ::core::result::Result::Ok(#value) => #value,
+                        ::core::result::Result::Err(_) => {
+                            ::core::unreachable!("required values are checked before building")
+                        }
+                    }
+                }))
+            }
+        }
+
+        impl #impl_generics ::core::default::Default for #matrix_name #type_generics #generic_where {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    }
+}
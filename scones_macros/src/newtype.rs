@@ -0,0 +1,263 @@
+//! Constructors and trait implementations for structs which wrap a single validated value.
+
+use crate::ConstructorInfo;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::token::Comma;
+use syn::{
+    parse_quote, Error, Fields, GenericArgument, Generics, Ident, ItemStruct, Lit, LitStr, Path,
+    PathArguments, Token, Type, TypePath,
+};
+
+/// The arguments of `#[make_newtype]`.
+#[derive(Default)]
+pub(crate) struct NewtypeInfo {
+    validate: Option<Path>,
+    error: Option<Type>,
+    deref: bool,
+    display: bool,
+    from_str: bool,
+}
+
+impl Parse for NewtypeInfo {
+    fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+        let mut result = Self::default();
+        while !input.is_empty() {
+            let name: Ident = input.parse()?;
+            match &name.to_string()[..] {
+                "validate" => {
+                    let _: Token![=] = input.parse()?;
+                    result.validate = Some(input.parse()?);
+                }
+                "error" => {
+                    let _: Token![=] = input.parse()?;
+                    result.error = Some(input.parse()?);
+                }
+                "deref" => result.deref = true,
+                "display" => result.display = true,
+                "from_str" => result.from_str = true,
+                _ => {
+                    return Err(Error::new_spanned(
+                        name,
+                        "Expected one of validate, error, deref, display or from_str.",
+                    ))
+                }
+            }
+            if !input.is_empty() {
+                let _: Comma = input.parse()?;
+            }
+        }
+        match (&result.validate, &result.error) {
+            (Some(_), None) => Err(Error::new(
+                input.span(),
+                "A newtype with a validate function also needs an error type, like \
+                `error = MyError`.",
+            )),
+            (None, Some(error)) => Err(Error::new_spanned(
+                error,
+                "An error type can only be given along with a validate function.",
+            )),
+            _ => Ok(result),
+        }
+    }
+}
+
+/// Adds `where #inner: #bound` to a copy of `generics`.
+fn with_bound(generics: &Generics, inner: &Type, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote! { #inner: #bound });
+    generics
+}
+
+/// Whether `ty` is one of the type parameters in `generics`, or a reference or box holding one.
+/// Other crates can implement `Into<Self>` for these, which would make an implementation of
+/// `TryFrom<ty>` overlap with the blanket one in the standard library.
+fn is_type_param(generics: &Generics, ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => {
+            if let Some(ident) = path.get_ident() {
+                return generics.type_params().any(|param| &param.ident == ident);
+            }
+            match path.segments.last() {
+                Some(segment) if segment.ident == "Box" => match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => matches!(
+                        args.args.first(),
+                        Some(GenericArgument::Type(ty)) if is_type_param(generics, ty)
+                    ),
+                    _ => false,
+                },
+                _ => false,
+            }
+        }
+        Type::Reference(reference) => is_type_param(generics, &reference.elem),
+        Type::Group(group) => is_type_param(generics, &group.elem),
+        Type::Paren(paren) => is_type_param(generics, &paren.elem),
+        _ => false,
+    }
+}
+
+/// The type of the single field of a newtype.
+fn inner_type(struct_def: &ItemStruct) -> Result<&Type, Error> {
+    match &struct_def.fields {
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok(&fields.unnamed[0].ty),
+        _ => Err(Error::new_spanned(
+            &struct_def.ident,
+            "Newtypes must be tuple structs with exactly one field, like `struct Port(u16);`.",
+        )),
+    }
+}
+
+/// Describes `try_new()`, or `new()` if there is no validate function, so that it is generated in
+/// the same way as any other constructor. Returns the constructor along with its documentation and
+/// the expression which initializes the field from the constructor's parameter.
+pub(crate) fn make_newtype_constructor(
+    info: &NewtypeInfo,
+    struct_def: &ItemStruct,
+) -> Result<(ConstructorInfo, Vec<Lit>, TokenStream2), Error> {
+    let inner = inner_type(struct_def)?;
+    let name = &struct_def.ident;
+    let vis = &struct_def.vis;
+    let value = format_ident!("inner");
+    let (constructor, doc, init) =
+        if let (Some(validate), Some(error)) = (&info.validate, &info.error) {
+            let doc = format!(
+                "Creates a new `{}`, returning an error if `{}` rejects the value.",
+                name,
+                quote! { #validate }.to_string().replace(' ', ""),
+            );
+            (
+                quote! { #vis try_new(#value: #inner) -> Result<Self, #error> },
+                doc,
+                quote! {{
+                    #validate(&#value)?;
+                    #value
+                }},
+            )
+        } else {
+            (
+                quote! { #vis new(#value: #inner) },
+                format!("Creates a new `{}`.", name),
+                quote! { #value },
+            )
+        };
+    let doc = Lit::Str(LitStr::new(&doc, Span::call_site()));
+    Ok((syn::parse2(constructor)?, vec![doc], init))
+}
+
+/// Generates everything for a newtype except its constructor, see `make_newtype_constructor()`.
+pub(crate) fn make_newtype_impl(
+    info: NewtypeInfo,
+    struct_def: &ItemStruct,
+) -> Result<TokenStream2, Error> {
+    let inner = inner_type(struct_def)?;
+    let name = &struct_def.ident;
+    let vis = &struct_def.vis;
+    let generics = &struct_def.generics;
+    let (impl_generics, type_generics, generic_where) = generics.split_for_impl();
+    let this = quote! { #name #type_generics };
+    let value = format_ident!("inner");
+
+    let mut inherent = Vec::new();
+    let mut traits = Vec::new();
+    if let Some(error) = &info.error {
+        inherent.push(quote! {
+            /// Creates a new instance without checking that the value is valid.
+            ///
+            /// # Safety
+            /// The value must be one which would be accepted by `try_new()`. Other code is allowed
+            /// to rely on every instance holding a valid value.
+            #vis unsafe fn new_unchecked(#value: #inner) -> Self {
+                Self(#value)
+            }
+        });
+        if !is_type_param(generics, inner) {
+            traits.push(quote! {
+                impl #impl_generics ::core::convert::TryFrom<#inner> for #this #generic_where {
+                    type Error = #error;
+
+                    fn try_from(#value: #inner) -> ::core::result::Result<Self, #error> {
+                        Self::try_new(#value)
+                    }
+                }
+            });
+        }
+    } else {
+        traits.push(quote! {
+            impl #impl_generics ::core::convert::From<#inner> for #this #generic_where {
+                fn from(#value: #inner) -> Self {
+                    Self(#value)
+                }
+            }
+        });
+    }
+    inherent.push(quote! {
+        /// Returns the value this item wraps.
+        #vis fn into_inner(self) -> #inner {
+            self.0
+        }
+    });
+    traits.push(quote! {
+        impl #impl_generics ::core::convert::AsRef<#inner> for #this #generic_where {
+            fn as_ref(&self) -> &#inner {
+                &self.0
+            }
+        }
+    });
+    if info.deref {
+        traits.push(quote! {
+            impl #impl_generics ::core::ops::Deref for #this #generic_where {
+                type Target = #inner;
+
+                fn deref(&self) -> &#inner {
+                    &self.0
+                }
+            }
+        });
+    }
+    if info.display {
+        let generics = with_bound(generics, inner, quote! { ::core::fmt::Display });
+        let (impl_generics, _, generic_where) = generics.split_for_impl();
+        traits.push(quote! {
+            impl #impl_generics ::core::fmt::Display for #this #generic_where {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Display::fmt(&self.0, f)
+                }
+            }
+        });
+    }
+    if info.from_str {
+        let generics = with_bound(generics, inner, quote! { ::core::str::FromStr });
+        let (impl_generics, _, generic_where) = generics.split_for_impl();
+        let parse_error = quote! { <#inner as ::core::str::FromStr>::Err };
+        let (err, body) = match &info.error {
+            Some(error) => (
+                quote! { ::scones::ParseNewtypeError<#parse_error, #error> },
+                quote! {
+                    let #value = s.parse().map_err(::scones::ParseNewtypeError::Parse)?;
+                    Self::try_new(#value).map_err(::scones::ParseNewtypeError::Invalid)
+                },
+            ),
+            None => (parse_error, quote! { s.parse().map(Self) }),
+        };
+        traits.push(quote! {
+            impl #impl_generics ::core::str::FromStr for #this #generic_where {
+                type Err = #err;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    #body
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics #this #generic_where {
+            #(#inherent)*
+        }
+        #(#traits)*
+    })
+}
@@ -0,0 +1,216 @@
+//! Builders whose required values are only checked when they are built, instead of at compile
+//! time. They are used when the values come from somewhere the compiler cannot see, like a
+//! configuration file.
+
+use crate::{
+    env, make_generic_args, make_generics_marker, unique_ident, BuilderField, RuntimeBuilder,
+};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::Ident;
+
+/// Generates code which gives the value of each parameter of the builder to its setter and then
+/// calls `build()`. `value_of` returns an expression for the value of a parameter, which must be an
+/// `Option`. The result of the code is `Ok` containing the result of `build()` if every required
+/// parameter has a value, otherwise it is `Err` containing a `Vec<&'static str>` with the names of
+/// the missing parameters.
+pub(crate) fn make_runtime_build(
+    builder: &RuntimeBuilder,
+    value_of: impl Fn(&Ident) -> TokenStream2,
+) -> TokenStream2 {
+    let builder_name = builder.builder_name;
+    let builder_var = Ident::new("builder", Span::mixed_site());
+    let missing = Ident::new("missing", Span::mixed_site());
+    let value = Ident::new("value", Span::mixed_site());
+    let mut required = Vec::new();
+    let mut missing_checks = Vec::new();
+    let mut optional_setters = Vec::new();
+    for field in builder.params {
+        let name = field.borrow_name();
+        let setter = &builder.setter_names[&name.to_string()];
+        let param_value = value_of(name);
+        if let BuilderField::Required { .. } = field {
+            let name_str = name.to_string();
+            missing_checks.push(quote! {
+                if #param_value.is_none() {
+                    #missing.push(#name_str);
+                }
+            });
+            required.push((name, setter, param_value));
+        } else {
+            optional_setters.push(quote! {
+                if let ::core::option::Option::Some(#value) = #param_value {
+                    #builder_var = #builder_var.#setter(#value);
+                }
+            });
+        }
+    }
+    let required_names: Vec<_> = required.iter().map(|(name, ..)| name).collect();
+    let required_values = required.iter().map(|(.., value)| value);
+    let required_setters = required
+        .iter()
+        .map(|(name, setter, _)| quote! { .#setter(#name) });
+    let missing_arm = if required.is_empty() {
+        quote! {}
+    } else {
+        quote! { _ => ::core::result::Result::Err(#missing), }
+    };
+    quote! {{
+        #[allow(unused_mut)]
+        let mut #missing: ::std::vec::Vec<&'static str> = ::std::vec::Vec::new();
+        #(#missing_checks)*
+        match (#(#required_values,)*) {
+            (#(::core::option::Option::Some(#required_names),)*) => {
+                #[allow(unused_mut)]
+                let mut #builder_var = #builder_name::new() #(#required_setters)*;
+                #(#optional_setters)*
+                ::core::result::Result::Ok(#builder_var.build())
+            }
+            #missing_arm
+        }
+    }}
+}
+
+/// Generates `<StructName>Partial`, which holds an `Option` for each parameter of the builder.
+pub(crate) fn make_partial(builder: &RuntimeBuilder) -> TokenStream2 {
+    let RuntimeBuilder {
+        vis,
+        struct_name,
+        generics,
+        builder_name,
+        ..
+    } = builder;
+    let partial_name = format_ident!("{}Partial", struct_name);
+    let value = Ident::new("value", Span::mixed_site());
+    let other = Ident::new("other", Span::mixed_site());
+    let result = Ident::new("result", Span::mixed_site());
+    let mut taken_fields = builder
+        .params
+        .iter()
+        .map(|field| field.borrow_name().to_string())
+        .collect();
+    let mut field_defs = Vec::new();
+    let mut initial_values = Vec::new();
+    let mut merged_values = Vec::new();
+    let mut setters = Vec::new();
+    for field in builder.params {
+        let name = field.borrow_name();
+        let ty = field.borrow_type();
+        let setter = &builder.setter_names[&name.to_string()];
+        field_defs.push(quote! { #vis #name: ::core::option::Option<#ty> });
+        initial_values.push(quote! { #name: ::core::option::Option::None });
+        merged_values.push(quote! { #name: #other.#name.or(self.#name) });
+        let doc = format!("Sets the value of `{}`.", name);
+        setters.push(quote! {
+            #[doc = #doc]
+            #vis fn #setter(mut self, #value: #ty) -> Self {
+                self.#name = ::core::option::Option::Some(#value);
+                self
+            }
+        });
+    }
+    // Only type and lifetime parameters need a marker. When there is one, it is public so that
+    // partial values can still be written as struct literals with `..Default::default()`.
+    if generics.type_params().next().is_some() || generics.lifetimes().next().is_some() {
+        let marker_field = unique_ident("generics_marker__", &mut taken_fields);
+        let marker = make_generics_marker(generics);
+        field_defs.push(quote! {
+            #[doc(hidden)]
+            pub #marker_field: #marker
+        });
+        initial_values.push(quote! { #marker_field: ::core::marker::PhantomData });
+        merged_values.push(quote! { #marker_field: ::core::marker::PhantomData });
+    }
+    let build = make_runtime_build(builder, |name| quote! { self.#name });
+    let (error, finish) = match builder.error_type {
+        Some(error_type) => (
+            quote! { ::scones::BuildError<#error_type> },
+            quote! { #result.map_err(::scones::BuildError::Failed) },
+        ),
+        None => (
+            quote! { ::scones::BuildError },
+            quote! { ::core::result::Result::Ok(#result) },
+        ),
+    };
+    let all_params: Vec<_> = builder.params.iter().collect();
+    let env_overrides = env::make_env_overrides(vis, builder.fields, &all_params);
+    let generic_args = make_generic_args(generics);
+    let (impl_generics, type_generics, generic_where) = generics.split_for_impl();
+    let (from_env, builder_from_env) = if env_overrides.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        let doc = format!(
+            "Reads every parameter from environment variables, see `{}::from_env()`.",
+            partial_name
+        );
+        let builder_from_env = quote! {
+            impl #impl_generics #builder_name <#(#generic_args),*> #generic_where {
+                #[doc = #doc]
+                #vis fn from_env() -> ::core::result::Result<
+                    #partial_name #type_generics,
+                    ::scones::Errors<::scones::EnvError>,
+                > {
+                    <#partial_name #type_generics>::from_env()
+                }
+            }
+        };
+        let from_env = quote! {
+            /// Creates a partial value from environment variables, see `env_overrides()`.
+            #vis fn from_env(
+            ) -> ::core::result::Result<Self, ::scones::Errors<::scones::EnvError>> {
+                Self::new().env_overrides()
+            }
+
+            #env_overrides
+        };
+        (from_env, builder_from_env)
+    };
+    let doc = format!(
+        "Holds values for some of the parameters of `{}`. Partial values can be merged together \
+        before building an instance of `{}`, and missing required values are only detected when \
+        `build()` is called.",
+        builder_name, struct_name,
+    );
+    quote! {
+        #[doc = #doc]
+        #vis struct #partial_name #generics #generic_where {
+            #(#field_defs,)*
+        }
+
+        impl #impl_generics #partial_name #type_generics #generic_where {
+            /// Creates a partial value where nothing has been set.
+            #vis fn new() -> Self {
+                Self {
+                    #(#initial_values),*
+                }
+            }
+
+            #(#setters)*
+
+            /// Combines two partial values. Values which are set in `other` replace the ones in
+            /// `self`.
+            #vis fn merge(self, #other: Self) -> Self {
+                Self {
+                    #(#merged_values),*
+                }
+            }
+
+            /// Builds the item, or returns an error listing the required values which were never
+            /// set.
+            #vis fn build(self) -> ::core::result::Result<#struct_name <#(#generic_args),*>, #error> {
+                let #result = #build.map_err(::scones::BuildError::Missing)?;
+                #finish
+            }
+
+            #from_env
+        }
+
+        impl #impl_generics ::core::default::Default for #partial_name #type_generics #generic_where {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        #builder_from_env
+    }
+}
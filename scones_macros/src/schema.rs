@@ -0,0 +1,115 @@
+//! `JsonSchema` implementations which describe the parameters of a builder.
+
+use crate::{BuilderField, RuntimeBuilder};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Expr, ExprLit, ExprMethodCall, ExprUnary, Lit, UnOp};
+
+/// Returns the value of a `#[value()]` expression as JSON, if it is a literal like `8080`, `-1.5`
+/// or `"text".to_owned()`.
+fn literal_default(init: &TokenStream2) -> Option<TokenStream2> {
+    let expr: Expr = syn::parse2(init.clone()).ok()?;
+    let (negative, lit) = match &expr {
+        Expr::Lit(ExprLit { lit, .. }) => (false, lit),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit { lit, .. }) => (true, lit),
+            _ => return None,
+        },
+        // Strings usually have to be converted into an owned value.
+        Expr::MethodCall(ExprMethodCall {
+            receiver,
+            method,
+            args,
+            ..
+        }) if args.is_empty()
+            && (method == "to_owned" || method == "to_string" || method == "into") =>
+        {
+            match &**receiver {
+                Expr::Lit(ExprLit {
+                    lit: lit @ Lit::Str(_),
+                    ..
+                }) => (false, lit),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    match lit {
+        Lit::Str(_) | Lit::Bool(_) if !negative => {}
+        Lit::Int(_) | Lit::Float(_) => {}
+        _ => return None,
+    }
+    let sign = if negative {
+        quote! { - }
+    } else {
+        quote! {}
+    };
+    Some(quote! { ::scones::serde_json__::Value::from(#sign #lit) })
+}
+
+/// Generates an implementation of `scones::JsonSchema` for the struct, which describes the map of
+/// parameters read by the builder.
+pub(crate) fn make_schema_impl(builder: &RuntimeBuilder) -> TokenStream2 {
+    let RuntimeBuilder {
+        struct_name,
+        generics,
+        ..
+    } = builder;
+    let builder_name = builder.builder_name.to_string();
+    let mut properties = Vec::new();
+    let mut required = Vec::new();
+    let mut generics = (*generics).clone();
+    for param in builder.params {
+        let name = param.borrow_name();
+        let ty = param.borrow_type();
+        let name_str = name.to_string();
+        let field = builder.fields.iter().find(|field| &field.ident == name);
+        let description = field.map(|field| field.doc.join("\n")).unwrap_or_default();
+        let default = match (param, field) {
+            (BuilderField::Override { .. }, Some(field)) => field
+                .custom_init
+                .get(&builder_name)
+                .or(field.default_init.as_ref())
+                .and_then(literal_default),
+            _ => None,
+        };
+        let default = match default {
+            Some(default) => quote! { ::core::option::Option::Some(#default) },
+            None => quote! { ::core::option::Option::None },
+        };
+        if let BuilderField::Required { .. } = param {
+            required.push(name_str.clone());
+        }
+        properties.push(quote! {
+            (
+                #name_str,
+                ::scones::schema_property__(
+                    <#ty as ::scones::JsonSchema>::json_schema(),
+                    #description,
+                    #default,
+                ),
+            )
+        });
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! { #ty: ::scones::JsonSchema });
+    }
+    let title = struct_name.to_string();
+    let (impl_generics, type_generics, generic_where) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::scones::JsonSchema for #struct_name #type_generics #generic_where {
+            fn json_schema() -> ::scones::serde_json__::Value {
+                ::scones::object_schema__(
+                    #title,
+                    ::std::vec![#(#properties),*],
+                    &[#(#required),*],
+                )
+            }
+        }
+    }
+}